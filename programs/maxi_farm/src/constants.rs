@@ -2,6 +2,7 @@ use std::str::FromStr;
 use anchor_lang::solana_program::pubkey::Pubkey;
 
 pub const NATIVE_MINT_2022_STR: &'static str = "9pan9bMn5HatX4EJdBwg9VgCa7Uz5HL8N1m5D3NdXejP"; // WSOL mint address
+pub const METADATA_PROGRAM_ID_STR: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"; // Metaplex token metadata program
 
 pub const FEE_PRE_DIV: u128 = 1000; // 1000 for 1%
 pub const BPS: u64 = 100; // 100 for 1%
@@ -9,15 +10,44 @@ pub const BPS: u64 = 100; // 100 for 1%
 pub const MAX_TAX: u64 = 50 * FEE_PRE_DIV as u64; // 50%
 pub const MAX_FEE_BPS: u64 = 100; // 1%
 pub const MAX_PRIV_SALE_PERIOD: u64 = 24 * 60 * 60; // 1d
+pub const MAX_PRIV_SALE_PHASES: usize = 4;
+pub const MAX_START_DELAY: u64 = 30 * 24 * 60 * 60; // 30d
+pub const MAX_CREATOR_ALLOCATION: u64 = 10 * BPS; // 10% of total supply
+pub const MAX_CREATOR_VESTING_DURATION: u64 = 4 * 365 * 24 * 60 * 60; // 4y
+pub const MAX_PRICE_PREMIUM: u64 = 50 * BPS; // 50%
+pub const MAX_TAX_INCREASE: u64 = 5 * FEE_PRE_DIV as u64; // 5% per update
+pub const TAX_UPDATE_COOLDOWN: u64 = 24 * 60 * 60; // 1d
+pub const MAX_TAX_DECAY_DURATION: u64 = 7 * 24 * 60 * 60; // 7d
 pub const MAX_TRADING_FEE: u64 = 5 * FEE_PRE_DIV as u64; // 5%
+pub const MAX_LAUNCH_FEE: u64 = 25 * FEE_PRE_DIV as u64; // 25%
+pub const MAX_LAUNCH_FEE_DURATION: u64 = 60 * 60; // 1h
+pub const MAX_FEE_TIERS: usize = 5;
+pub const MAX_VOLUME_DISCOUNT: u64 = 50 * BPS; // 50% of trading fee
+pub const MAX_BUYBACK_SHARE: u64 = 50 * BPS; // 50% of trading fee
+pub const MAX_BUYBACK_PRICE_IMPACT: u64 = 5 * BPS; // 5%
+pub const BUYBACK_VAULT_SEED: &[u8] = b"buyback"; // Seed of buyback vault (system-owned PDA)
+pub const MAX_PLATFORM_FEE_SHARE: u64 = 50 * BPS; // 50% of trading fee
+pub const MAX_HOLDER_DISCOUNT: u64 = 50 * BPS; // 50% of trading fee
+pub const VOLUME_WINDOW_DAYS: usize = 30;
+pub const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
 
 pub const DEF_PRIV_SALE_PERIOD: u64 = /* 30 */ 0 * 60; // /* 30 */ 0 min
 pub const DEF_TIER1_REWARD: u64 = 25 * BPS; // 25%
 pub const DEF_TIER2_REWARD: u64 =  7 * BPS / 2; // 3.5%
 pub const DEF_TIER3_REWARD: u64 = 3 * BPS; // 3%
+pub const DEF_MAX_BUY_TAX: u64 = 10 * FEE_PRE_DIV as u64; // 10%
+pub const DEF_MAX_SELL_TAX: u64 = 10 * FEE_PRE_DIV as u64; // 10%
+pub const MAX_TIER1_REWARD: u64 = 50 * BPS; // 50%
+pub const MAX_REWARD_VESTING_DURATION: u64 = 180 * 24 * 60 * 60; // 180d
+pub const MAX_REFEREE_DISCOUNT: u64 = 100 * BPS - MAX_TIER1_REWARD; // 50%, the rest of trading fee is for referral rewards
 
 pub const NORMAL_REAL_QUOTE_THRESHOLD: u64 = 82_000_000_000; // 82 SOL
 
-pub const PUBKEY_OFFSET: usize = 2 * 1 + 7 * 2;
+pub const SIG_OFFSETS_START: usize = 2; // num_signatures (u8) + padding (u8)
+pub const SIG_OFFSETS_LEN: usize = 7 * 2; // 7 * u16 per signature
 pub const PUBKEY_LEN: usize = 32;
 pub const SIG_LEN: usize = 64;
+pub const SECP256K1_OFFSETS_START: usize = 1; // num_signatures (u8)
+pub const SECP256K1_OFFSETS_LEN: usize = 4 * 2 + 3; // 4 * u16 + 3 * u8 per signature
+pub const ETH_ADDRESS_LEN: usize = 20;
+pub const SECP256K1_SIG_LEN: usize = 64 + 1; // signature + recovery id
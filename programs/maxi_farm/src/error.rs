@@ -61,6 +61,78 @@ pub enum MaxiFarmError {
     InvalidSigLen,
     #[msg("Signature verification failed")]
     SigVerificationFailed,
+    #[msg("Invalid permit")]
+    InvalidPermit,
+    #[msg("Permit belongs to another wallet")]
+    PermitWrongTrader,
+    #[msg("Permit expired")]
+    PermitExpired,
+    #[msg("Exceeded permit amount")]
+    PermitExceeded,
+    #[msg("Missing permit nonce account")]
+    MissingPermitNonce,
+    #[msg("Permit already used")]
+    PermitAlreadyUsed,
+    #[msg("Invalid allowlist proof")]
+    InvalidAllowlistProof,
+    #[msg("Missing allowlist allocation account")]
+    MissingAllocationAccount,
+    #[msg("Exceeded allowlist allocation")]
+    ExceededAllocation,
+    #[msg("Secp256k1 signer is not set")]
+    Secp256k1SignerNotSet,
+    #[msg("Invalid private sale phases")]
+    InvalidPrivSalePhases,
+    #[msg("Invalid start time")]
+    InvalidStartTime,
+    #[msg("Trading not started")]
+    TradingNotStarted,
+    #[msg("Pool already traded")]
+    PoolAlreadyTraded,
+    #[msg("Missing accounts for initial buy")]
+    MissingInitialBuyAccounts,
+    #[msg("Invalid creator allocation")]
+    InvalidCreatorAllocation,
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+    #[msg("BondingCurve not withdrawn")]
+    BondingCurveNotWithdrawn,
+    #[msg("Invalid tax schedule")]
+    InvalidTaxSchedule,
+    #[msg("Tax schedule can only be lowered")]
+    TaxScheduleNotLowered,
+    #[msg("Tax change too large")]
+    TaxChangeTooLarge,
+    #[msg("Tax update cooldown not passed")]
+    TaxUpdateTooSoon,
+    #[msg("Pool owner powers renounced")]
+    OwnerRenounced,
+    #[msg("Invalid launch fee")]
+    InvalidLaunchFee,
+    #[msg("Invalid fee tiers")]
+    InvalidFeeTiers,
+    #[msg("Invalid trader stats account")]
+    InvalidTraderStats,
+    #[msg("Invalid token gate")]
+    InvalidTokenGate,
+    #[msg("Invalid token gate account")]
+    InvalidTokenGateAccount,
+    #[msg("Token gate not satisfied")]
+    TokenGateNotSatisfied,
+    #[msg("Invalid platform fee share")]
+    InvalidPlatformFee,
+    #[msg("Invalid buyback config")]
+    InvalidBuybackConfig,
+    #[msg("Missing buyback vault")]
+    MissingBuybackVault,
+    #[msg("Buyback unavailable")]
+    BuybackUnavailable,
+    #[msg("Tax can't be raised while launch tax decays")]
+    TaxScheduleActive,
+    #[msg("Trading already started")]
+    TradingStarted,
 
     #[msg("Too few output tokens")]
     TooFewOutputTokens,
@@ -81,5 +153,23 @@ pub enum MaxiFarmError {
     InsufficientRealQuoteReserves,
 
     #[msg("No rewards available")]
-    NoRewardsAvailable
+    NoRewardsAvailable,
+    #[msg("Invalid referral reward")]
+    InvalidReferralReward,
+    #[msg("Invalid expiry")]
+    InvalidExpiry,
+    #[msg("Invalid referrer")]
+    InvalidReferrer,
+    #[msg("Invalid referee discount")]
+    InvalidRefereeDiscount,
+    #[msg("Invalid claim accounts")]
+    InvalidClaimAccounts,
+    #[msg("Invalid reward vesting duration")]
+    InvalidRewardVesting,
+    #[msg("Invalid reward expiry period")]
+    InvalidRewardExpiry,
+    #[msg("Rewards expired")]
+    RewardsExpired,
+    #[msg("Rewards not expired")]
+    RewardsNotExpired
 }
@@ -5,6 +5,7 @@ use anchor_lang::prelude::*;
 pub mod main_state;
 pub mod pool;
 pub mod referral;
+pub mod platform;
 
 pub mod constants;
 pub mod error;
@@ -13,6 +14,7 @@ pub mod utils;
 use main_state::*;
 use pool::*;
 use referral::*;
+use platform::*;
 
 declare_id!("maxi7YSyG2Fpuh9hNzjojuV7woB9PTV4fRwZZ523ayu");
 
@@ -32,25 +34,94 @@ pub mod maxi_farm {
         main_state::update_main_state(ctx, input)
     }
 
+    pub fn migrate_main_state(ctx: Context<AMigrateMainState>) -> Result<()> {
+        main_state::migrate_main_state(ctx)
+    }
+
+    pub fn set_fee_tiers(ctx: Context<ASetFeeTiers>, tiers: Vec<FeeTier>) -> Result<()> {
+        main_state::set_fee_tiers(ctx, tiers)
+    }
+
+    pub fn set_token_gate(ctx: Context<ASetTokenGate>, gate: TokenGate) -> Result<()> {
+        main_state::set_token_gate(ctx, gate)
+    }
+
+    pub fn set_buyback_config(ctx: Context<ASetBuybackConfig>, share: u64, mint: Pubkey, max_amount: u64, max_price_impact: u64) -> Result<()> {
+        main_state::set_buyback_config(ctx, share, mint, max_amount, max_price_impact)
+    }
+
     
-    pub fn create_pool(ctx: Context<ACreatePool>, metadata_uri: String, tax_bps: u64, max_fee_tokens: u64, real_quote_threshold: u64, coin_type: u8, priv_sale_period: Option<u64>) -> Result<()> {
-        pool::create_pool(ctx, metadata_uri, tax_bps, max_fee_tokens, real_quote_threshold, coin_type, priv_sale_period)
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_pool(ctx: Context<ACreatePool>, metadata_uri: String, buy_tax: u64, sell_tax: u64, max_fee_tokens: u64, real_quote_threshold: u64, coin_type: u8, priv_sale_period: Option<u64>, start_time: Option<u64>, initial_buy: Option<InitialBuy>, vesting: Option<VestingSchedule>, tax_schedule: Option<TaxSchedule>, allowlist_root: Option<[u8; 32]>) -> Result<()> {
+        pool::create_pool(ctx, metadata_uri, buy_tax, sell_tax, max_fee_tokens, real_quote_threshold, coin_type, priv_sale_period, start_time, initial_buy, vesting, tax_schedule, allowlist_root)
     }
 
-    pub fn buy_tokens_from_exact_sol(ctx: Context<ABuy>, quote_amount: u64, min_base_amount: u64, tax_bps: u64, sig: Option<Vec<u8>>) -> Result<()> {
-        pool::buy_tokens_from_exact_sol(ctx, quote_amount, min_base_amount, tax_bps, sig)
+    pub fn buy_tokens_from_exact_sol(ctx: Context<ABuy>, quote_amount: u64, min_base_amount: u64, auth: Option<PrivSaleAuth>) -> Result<()> {
+        pool::buy_tokens_from_exact_sol(ctx, quote_amount, min_base_amount, auth)
     }
 
-    pub fn buy_exact_tokens_from_sol(ctx: Context<ABuy>, base_amount: u64, max_quote_amount: u64, tax_bps: u64, sig: Option<Vec<u8>>) -> Result<()> {
-        pool::buy_exact_tokens_from_sol(ctx, base_amount, max_quote_amount, tax_bps, sig)
+    pub fn buy_exact_tokens_from_sol(ctx: Context<ABuy>, base_amount: u64, max_quote_amount: u64, auth: Option<PrivSaleAuth>) -> Result<()> {
+        pool::buy_exact_tokens_from_sol(ctx, base_amount, max_quote_amount, auth)
     }
 
-    pub fn sell(ctx: Context<ASell>, amount: u64, min_sol_output: u64, tax_bps: u64, sig: Option<Vec<u8>>) -> Result<()> {
-        pool::sell(ctx, amount, min_sol_output, tax_bps, sig)
+    pub fn sell(ctx: Context<ASell>, amount: u64, min_sol_output: u64, auth: Option<PrivSaleAuth>) -> Result<()> {
+        pool::sell(ctx, amount, min_sol_output, auth)
     }
     
-    pub fn update_tax(ctx: Context<AUpdateTax>, new_tax: u64) -> Result<()> {
-        pool::update_tax(ctx, new_tax)
+    pub fn update_tax(ctx: Context<AUpdateTax>, buy_tax: u64, sell_tax: u64, tax_schedule: Option<TaxSchedule>) -> Result<()> {
+        pool::update_tax(ctx, buy_tax, sell_tax, tax_schedule)
+    }
+
+    pub fn renounce_tax_authority(ctx: Context<ARenounceTaxAuthority>) -> Result<()> {
+        pool::renounce_tax_authority(ctx)
+    }
+
+    pub fn propose_pool_owner(ctx: Context<AProposePoolOwner>, new_owner: Pubkey) -> Result<()> {
+        pool::propose_pool_owner(ctx, new_owner)
+    }
+
+    pub fn accept_pool_owner(ctx: Context<AAcceptPoolOwner>) -> Result<()> {
+        pool::accept_pool_owner(ctx)
+    }
+
+    pub fn claim_vested(ctx: Context<AClaimVested>) -> Result<()> {
+        pool::claim_vested(ctx)
+    }
+
+    pub fn cancel_pool(ctx: Context<ACancelPool>) -> Result<()> {
+        pool::cancel_pool(ctx)
+    }
+
+    pub fn close_pool(ctx: Context<AClosePool>) -> Result<()> {
+        pool::close_pool(ctx)
+    }
+
+    pub fn set_allowlist_root(ctx: Context<ASetAllowlistRoot>, root: [u8; 32]) -> Result<()> {
+        pool::set_allowlist_root(ctx, root)
+    }
+
+    pub fn set_priv_sale_phases(ctx: Context<ASetPrivSalePhases>, phases: Vec<PrivSalePhase>) -> Result<()> {
+        pool::set_priv_sale_phases(ctx, phases)
+    }
+
+    pub fn set_pool_trading_fee(ctx: Context<ASetPoolTradingFee>, trading_fee: Option<u64>) -> Result<()> {
+        pool::set_pool_trading_fee(ctx, trading_fee)
+    }
+
+    pub fn set_pool_token_gate(ctx: Context<ASetPoolTokenGate>, gate: TokenGate) -> Result<()> {
+        pool::set_pool_token_gate(ctx, gate)
+    }
+
+    pub fn execute_buyback(ctx: Context<AExecuteBuyback>, min_base_amount: u64) -> Result<()> {
+        pool::execute_buyback(ctx, min_base_amount)
+    }
+
+    pub fn get_priv_sale_phase(ctx: Context<AGetPrivSalePhase>) -> Result<Option<PrivSalePhaseInfo>> {
+        pool::get_priv_sale_phase(ctx)
+    }
+
+    pub fn migrate_pool_state(ctx: Context<AMigratePoolState>, base_mint: Pubkey) -> Result<()> {
+        pool::migrate_pool_state(ctx, base_mint)
     }
     
     pub fn force_complete(ctx: Context<AForceComplete>) -> Result<()> {
@@ -66,6 +137,39 @@ pub mod maxi_farm {
         referral::claim_rewards(ctx)
     }
 
+    pub fn claim_rewards_batch(ctx: Context<AClaimRewardsBatch>) -> Result<()> {
+        referral::claim_rewards_batch(ctx)
+    }
+
+    pub fn set_claim_config(ctx: Context<ASetClaimConfig>, delegate: Pubkey, destination: Pubkey) -> Result<()> {
+        referral::set_claim_config(ctx, delegate, destination)
+    }
+
+    pub fn sweep_expired_rewards(ctx: Context<ASweepExpiredRewards>) -> Result<()> {
+        referral::sweep_expired_rewards(ctx)
+    }
+
+    pub fn set_custom_reward(ctx: Context<ASetCustomReward>, referrer: Pubkey, tier1_reward: u64, expiry: u64) -> Result<()> {
+        referral::set_custom_reward(ctx, referrer, tier1_reward, expiry)
+    }
+
+    pub fn migrate_referral_state(ctx: Context<AMigrateReferralState>, user: Pubkey) -> Result<()> {
+        referral::migrate_referral_state(ctx, user)
+    }
+
+
+    pub fn register_platform(ctx: Context<ARegisterPlatform>, platform: Pubkey, fee_share: u64) -> Result<()> {
+        platform::register_platform(ctx, platform, fee_share)
+    }
+
+    pub fn set_platform_fee(ctx: Context<ASetPlatformFee>, fee_share: u64) -> Result<()> {
+        platform::set_platform_fee(ctx, fee_share)
+    }
+
+    pub fn claim_platform_fees(ctx: Context<AClaimPlatformFees>) -> Result<()> {
+        platform::claim_platform_fees(ctx)
+    }
+
     
     pub fn withdraw(ctx: Context<AWithdraw>) -> Result<()> {
         pool::withdraw(ctx)
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::{FeeTier, TokenGate};
 
 // MainState initialization event
 #[event]
@@ -18,6 +19,29 @@ pub struct OwnershipTransferred {
     pub new_owner: Pubkey
 }
 
+// Fee tiers updated event
+#[event]
+pub struct FeeTiersUpdated {
+    pub authority: Pubkey,
+    pub tiers: Vec<FeeTier>
+}
+
+// Protocol token gate updated event
+#[event]
+pub struct TokenGateUpdated {
+    pub authority: Pubkey,
+    pub gate: TokenGate
+}
+
+// Buyback config updated event
+#[event]
+pub struct BuybackConfigUpdated {
+    pub share: u64,
+    pub mint: Pubkey,
+    pub max_amount: u64,
+    pub max_price_impact: u64
+}
+
 // MainState updated event
 #[event]
 pub struct MainStateUpdated {
@@ -25,5 +49,20 @@ pub struct MainStateUpdated {
     pub withdrawer: Pubkey,
     
     pub trading_fee: u64,
-    pub fee_recipient: Pubkey
+    pub fee_recipient: Pubkey,
+
+    pub referral_manager: Pubkey,
+    pub referee_discount: u64,
+    pub reward_vesting_duration: u64,
+    pub reward_expiry_period: u64,
+
+    pub secp256k1_signer: [u8; 20],
+
+    pub max_buy_tax: u64,
+    pub max_sell_tax: u64,
+
+    pub launch_fee: u64,
+    pub launch_fee_duration: u64,
+
+    pub fee_manager: Pubkey
 }
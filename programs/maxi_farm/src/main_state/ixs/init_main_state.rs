@@ -6,7 +6,7 @@ use anchor_spl::{
 };
 use std::str::FromStr;
 use crate::{
-    constants::{FEE_PRE_DIV, DEF_TIER1_REWARD, DEF_TIER2_REWARD, DEF_TIER3_REWARD},
+    constants::{FEE_PRE_DIV, DEF_TIER1_REWARD, DEF_TIER2_REWARD, DEF_TIER3_REWARD, DEF_MAX_BUY_TAX, DEF_MAX_SELL_TAX},
     error::MaxiFarmError,
     MainState,
     MainStateInitialized
@@ -31,6 +31,13 @@ pub fn init_main_state(ctx: Context<AInitMainState>, signer: Pubkey) -> Result<(
     state.tier1_reward = DEF_TIER1_REWARD;
     state.tier2_reward = DEF_TIER2_REWARD;
     state.tier3_reward = DEF_TIER3_REWARD;
+
+    state.referral_manager = ctx.accounts.owner.key();
+
+    state.max_buy_tax = DEF_MAX_BUY_TAX;
+    state.max_sell_tax = DEF_MAX_SELL_TAX;
+
+    state.fee_manager = ctx.accounts.owner.key();
     
     emit!(MainStateInitialized {
         owner: state.owner,
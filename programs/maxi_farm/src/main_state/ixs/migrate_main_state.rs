@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::{DEF_MAX_BUY_TAX, DEF_MAX_SELL_TAX},
+    error::MaxiFarmError,
+    utils::realloc_account,
+    MainState
+};
+
+// This function migrates main state to the current layout
+//   New members are zero-initialized, except the ones init_main_state sets defaults for
+// Params
+//   ctx - MainState migration context
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn migrate_main_state(ctx: Context<AMigrateMainState>) -> Result<()> {
+    let main_state = ctx.accounts.main_state.to_account_info();
+
+    // Owner is the first member, right after the discriminator
+    let owner = Pubkey::try_from(&main_state.try_borrow_data()?[8..40]).unwrap();
+    require!(owner.eq(&ctx.accounts.owner.key()), MaxiFarmError::Unauthorised);
+    let is_initial_layout = main_state.data_len() == 8 + MainState::INITIAL_SIZE;
+
+    realloc_account(
+        main_state.clone(),
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        8 + MainState::MAX_SIZE
+    )?;
+
+    if is_initial_layout {
+        // Same defaults as init_main_state
+        let mut data = main_state.try_borrow_mut_data()?;
+        let mut state = MainState::try_deserialize(&mut &data[..])?;
+        state.referral_manager = owner;
+        state.max_buy_tax = DEF_MAX_BUY_TAX;
+        state.max_sell_tax = DEF_MAX_SELL_TAX;
+        state.fee_manager = owner;
+        state.try_serialize(&mut &mut data[..])?;
+    }
+
+    Ok(())
+}
+
+// MainState migration context - passed with accounts
+#[derive(Accounts)]
+pub struct AMigrateMainState<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>, // Current owner
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+        owner = crate::ID
+    )]
+    /// CHECK: deserialized after migration
+    pub main_state: UncheckedAccount<'info>, // MainState account in the previous layout
+
+    pub system_program: Program<'info, System>
+}
@@ -6,3 +6,15 @@ pub use transfer_ownership::*;
 
 pub mod update_main_state;
 pub use update_main_state::*;
+
+pub mod migrate_main_state;
+pub use migrate_main_state::*;
+
+pub mod set_fee_tiers;
+pub use set_fee_tiers::*;
+
+pub mod set_token_gate;
+pub use set_token_gate::*;
+
+pub mod set_buyback_config;
+pub use set_buyback_config::*;
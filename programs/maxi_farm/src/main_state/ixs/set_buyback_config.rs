@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use solana_program::{
+    system_instruction,
+    program::invoke,
+};
+use crate::{
+    constants::{MAX_BUYBACK_SHARE, MAX_BUYBACK_PRICE_IMPACT, BUYBACK_VAULT_SEED},
+    error::MaxiFarmError,
+    MainState,
+    BuybackConfigUpdated
+};
+
+// This function sets buyback-and-burn config
+// Params
+//   ctx - SetBuybackConfig context
+//   share - Share of trading fee sent to buyback vault (0 to disable)
+//   mint - Token bought back and burnt
+//   max_amount - Max. amount of SOL spent per buyback (0 for no limit)
+//   max_price_impact - Max. price impact of a buyback (must be set to enable buyback)
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn set_buyback_config(ctx: Context<ASetBuybackConfig>, share: u64, mint: Pubkey, max_amount: u64, max_price_impact: u64) -> Result<()> {
+    require!(
+        share.le(&MAX_BUYBACK_SHARE)
+            && max_price_impact.le(&MAX_BUYBACK_PRICE_IMPACT)
+            && (share == 0 || (mint != Pubkey::default() && max_price_impact > 0)),
+        MaxiFarmError::InvalidBuybackConfig
+    );
+
+    // Vault must stay rent exempt to receive small fee transfers
+    let buyback_vault = ctx.accounts.buyback_vault.to_account_info();
+    let required_lamports = Rent::get()?.minimum_balance(0);
+    if buyback_vault.lamports() < required_lamports {
+        invoke(
+            &system_instruction::transfer(
+                &ctx.accounts.owner.key(),
+                buyback_vault.key,
+                required_lamports - buyback_vault.lamports()
+            ),
+            &[
+                ctx.accounts.owner.to_account_info(),
+                buyback_vault,
+                ctx.accounts.system_program.to_account_info()
+            ]
+        )?;
+    }
+
+    let main_state = &mut ctx.accounts.main_state;
+    main_state.buyback_share = share;
+    main_state.buyback_mint = mint;
+    main_state.buyback_max_amount = max_amount;
+    main_state.buyback_max_price_impact = max_price_impact;
+
+    emit!(BuybackConfigUpdated {
+        share,
+        mint,
+        max_amount,
+        max_price_impact
+    });
+
+    Ok(())
+}
+
+// Buyback config update context - passed with accounts
+#[derive(Accounts)]
+pub struct ASetBuybackConfig<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>, // Current owner
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+        has_one = owner
+    )]
+    pub main_state: Box<Account<'info, MainState>>, // MainState account
+
+    #[account(
+        mut,
+        seeds = [BUYBACK_VAULT_SEED],
+        bump
+    )]
+    /// CHECK: system-owned PDA holding SOL for buybacks
+    pub buyback_vault: UncheckedAccount<'info>, // Buyback vault
+
+    pub system_program: Program<'info, System>
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::{
+    error::MaxiFarmError,
+    MainState, FeeTier,
+    FeeTiersUpdated
+};
+
+// This function sets volume-based fee tiers
+// Params
+//   ctx - SetFeeTiers context
+//   tiers - Fee tiers, ordered by min. volume (empty to disable)
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn set_fee_tiers(ctx: Context<ASetFeeTiers>, tiers: Vec<FeeTier>) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    main_state.set_fee_tiers(&tiers)?;
+
+    emit!(FeeTiersUpdated {
+        authority: ctx.accounts.authority.key(),
+        tiers
+    });
+
+    Ok(())
+}
+
+// Fee tiers update context - passed with accounts
+#[derive(Accounts)]
+pub struct ASetFeeTiers<'info> {
+    #[account(
+        constraint = authority.key() == main_state.owner || authority.key() == main_state.fee_manager @ MaxiFarmError::Unauthorised
+    )]
+    pub authority: Signer<'info>, // Owner or fee manager
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>> // MainState account
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::{
+    MainState, TokenGate,
+    TokenGateUpdated
+};
+
+// This function sets protocol-wide benefits for token holders
+// Params
+//   ctx - SetTokenGate context
+//   gate - New token gate (zero key to disable)
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn set_token_gate(ctx: Context<ASetTokenGate>, gate: TokenGate) -> Result<()> {
+    gate.validate()?;
+
+    let main_state = &mut ctx.accounts.main_state;
+    main_state.token_gate = gate;
+
+    emit!(TokenGateUpdated {
+        authority: ctx.accounts.owner.key(),
+        gate
+    });
+
+    Ok(())
+}
+
+// Token gate update context - passed with accounts
+#[derive(Accounts)]
+pub struct ASetTokenGate<'info> {
+    #[account()]
+    pub owner: Signer<'info>, // Current owner
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+        has_one = owner
+    )]
+    pub main_state: Box<Account<'info, MainState>> // MainState account
+}
@@ -6,7 +6,7 @@ use anchor_spl::{
 };
 use std::str::FromStr;
 use crate::{
-    constants::{MAX_TRADING_FEE},
+    constants::{MAX_TAX, MAX_TRADING_FEE, MAX_LAUNCH_FEE, MAX_LAUNCH_FEE_DURATION, MAX_REFEREE_DISCOUNT, MAX_REWARD_VESTING_DURATION},
     error::MaxiFarmError,
     MainState,
     MainStateUpdated
@@ -18,7 +18,17 @@ pub struct UpdateMainStateInput {
     signer: Pubkey,         // New signer
     withdrawer: Pubkey,     // New withdrawer
     trading_fee: u64,       // New trading fee
-    fee_recipient: Pubkey   // New fee recipient
+    fee_recipient: Pubkey,  // New fee recipient
+    referral_manager: Pubkey, // New referral manager
+    referee_discount: u64,  // New referee discount
+    reward_vesting_duration: u64, // New referral reward vesting duration
+    reward_expiry_period: u64, // New referral reward expiry period
+    secp256k1_signer: [u8; 20], // New secp256k1 signer (Ethereum address)
+    max_buy_tax: u64,       // New maximum buy tax of pools
+    max_sell_tax: u64,      // New maximum sell tax of pools
+    launch_fee: u64,        // New launch fee (0: disabled)
+    launch_fee_duration: u64, // New launch fee decay duration
+    fee_manager: Pubkey     // New fee manager
 }
 
 // This function updates main state
@@ -35,6 +45,29 @@ pub fn update_main_state(
         input.trading_fee.le(&MAX_TRADING_FEE),
         MaxiFarmError::InvalidTradingFee
     );
+    require!(
+        input.referee_discount.le(&MAX_REFEREE_DISCOUNT),
+        MaxiFarmError::InvalidRefereeDiscount
+    );
+    require!(
+        input.reward_vesting_duration.le(&MAX_REWARD_VESTING_DURATION),
+        MaxiFarmError::InvalidRewardVesting
+    );
+    // Rewards must be able to vest before they expire
+    require!(
+        input.reward_expiry_period == 0 || input.reward_expiry_period > input.reward_vesting_duration,
+        MaxiFarmError::InvalidRewardExpiry
+    );
+    require!(
+        input.max_buy_tax.le(&MAX_TAX) && input.max_sell_tax.le(&MAX_TAX),
+        MaxiFarmError::InvalidTax
+    );
+    require!(
+        (input.launch_fee == 0 || input.launch_fee.ge(&input.trading_fee))
+            && input.launch_fee.le(&MAX_LAUNCH_FEE)
+            && input.launch_fee_duration.le(&MAX_LAUNCH_FEE_DURATION),
+        MaxiFarmError::InvalidLaunchFee
+    );
     
     let main_state = &mut ctx.accounts.main_state;
 
@@ -44,13 +77,43 @@ pub fn update_main_state(
     
     main_state.trading_fee = input.trading_fee;
     main_state.fee_recipient = input.fee_recipient;
+
+    main_state.referral_manager = input.referral_manager;
+    main_state.referee_discount = input.referee_discount;
+    main_state.reward_vesting_duration = input.reward_vesting_duration;
+    main_state.reward_expiry_period = input.reward_expiry_period;
+
+    main_state.secp256k1_signer = input.secp256k1_signer;
+
+    main_state.max_buy_tax = input.max_buy_tax;
+    main_state.max_sell_tax = input.max_sell_tax;
+
+    main_state.launch_fee = input.launch_fee;
+    main_state.launch_fee_duration = input.launch_fee_duration;
+
+    main_state.fee_manager = input.fee_manager;
     
     emit!(MainStateUpdated {
         signer: input.signer,
         withdrawer: input.withdrawer,
         
         trading_fee: input.trading_fee,
-        fee_recipient: input.fee_recipient
+        fee_recipient: input.fee_recipient,
+
+        referral_manager: input.referral_manager,
+        referee_discount: input.referee_discount,
+        reward_vesting_duration: input.reward_vesting_duration,
+        reward_expiry_period: input.reward_expiry_period,
+
+        secp256k1_signer: input.secp256k1_signer,
+
+        max_buy_tax: input.max_buy_tax,
+        max_sell_tax: input.max_sell_tax,
+
+        launch_fee: input.launch_fee,
+        launch_fee_duration: input.launch_fee_duration,
+
+        fee_manager: input.fee_manager
     });
     
     Ok(())
@@ -1,4 +1,16 @@
 use anchor_lang::prelude::*;
+use crate::{
+    constants::{BPS, MAX_FEE_TIERS, MAX_VOLUME_DISCOUNT},
+    error::MaxiFarmError,
+    TokenGate
+};
+
+// Volume-based fee tier
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeeTier {
+    pub min_volume: u64,            // Min. rolling SOL volume of the tier
+    pub discount: u64               // Discount on trading fee (100 = 1%)
+}
 
 // Main state of Program
 #[account]
@@ -13,9 +25,142 @@ pub struct MainState {
     pub tier1_reward: u64,              // Tier1 reward percent (25%)
     pub tier2_reward: u64,              // Tier2 reward percent (3.5%)
     pub tier3_reward: u64,              // Tier3 reward percent (3%)
+
+    pub referral_manager: Pubkey,       // Address of referral manager (Sets custom referral rewards)
+    pub referee_discount: u64,          // Trading fee discount percent for referred traders (taken from protocol share)
+    pub reward_vesting_duration: u64,   // Referral rewards vest linearly over this duration (0: claimable at once)
+    pub reward_expiry_period: u64,      // Claimable referral rewards expire after this period (0: never expire)
+
+    pub secp256k1_signer: [u8; 20],     // Ethereum address of secp256k1 signer (zero: disabled)
+
+    pub max_buy_tax: u64,               // Maximum buy tax a pool owner can set
+    pub max_sell_tax: u64,              // Maximum sell tax a pool owner can set
+
+    pub launch_fee: u64,                // Trading fee at trading start, decaying to trading_fee (0: disabled)
+    pub launch_fee_duration: u64,       // Duration of launch fee decay, from trading start
+
+    pub fee_manager: Pubkey,            // Address of fee manager (Sets per-pool trading fee overrides and fee tiers)
+
+    pub num_fee_tiers: u8,              // Number of volume-based fee tiers
+    pub fee_tiers: [FeeTier; MAX_FEE_TIERS], // Volume-based fee tiers, ordered by min. volume
+
+    pub token_gate: TokenGate,          // Protocol-wide benefits for token holders
+
+    pub buyback_share: u64,             // Share of trading fee sent to buyback vault (0: disabled)
+    pub buyback_mint: Pubkey,           // Token bought back and burnt along its bonding curve
+    pub buyback_max_amount: u64,        // Max. amount of SOL spent per buyback (0: no limit)
+
+    pub total_pools_created: u64,       // Number of created pools
+    pub total_pools_completed: u64,     // Number of completed bonding curves
+    pub total_quote_volume: u64,        // Cumulative traded amount of SOL
+    pub total_fees_collected: u64,      // Cumulative trading fees
+    pub total_rewards_accrued: u64,     // Cumulative referral rewards credited to referrers
+    pub total_rewards_claimed: u64,     // Cumulative referral rewards claimed
+    pub total_base_withdrawn: u64,      // Cumulative amount of tokens withdrawn from completed bonding curves
+
+    pub buyback_max_price_impact: u64,  // Max. price impact of a buyback (100 = 1%)
+
+    pub total_rewards_swept: u64,       // Cumulative expired referral rewards swept to fee recipient
 }
 
 impl MainState {
     pub const MAX_SIZE: usize = std::mem::size_of::<Self>();    // Size of MainState
+    pub const INITIAL_SIZE: usize = 160;                        // Size of MainState in the initial layout
     pub const PREFIX_SEED: &'static [u8] = b"main";             // Seed of MainState
+
+    // This function returns trading fee in effect for a pool (shared with off-chain quoting)
+    // Params
+    //   self - MainState struct itself
+    //   fee_override - Trading fee override of the pool
+    //   start_time - Trading start time of the pool
+    //   timestamp - Time of the trade
+    // Return
+    //   Launch fee decaying linearly to pool's trading fee, or pool's trading fee after decay
+    pub fn base_trading_fee(&self, fee_override: Option<u64>, start_time: u64, timestamp: u64) -> u64 {
+        let trading_fee = fee_override.unwrap_or(self.trading_fee);
+        let elapsed = timestamp.saturating_sub(start_time);
+        if self.launch_fee <= trading_fee || elapsed >= self.launch_fee_duration {
+            return trading_fee;
+        }
+        let remaining = self.launch_fee_duration - elapsed;
+        trading_fee + (self.launch_fee - trading_fee) * remaining / self.launch_fee_duration
+    }
+
+    // This function sets volume-based fee tiers
+    // Params
+    //   self - MainState struct itself
+    //   tiers - Fee tiers, ordered by min. volume (empty to disable)
+    // Return
+    //   Ok on success, ErrorCode on failure
+    pub fn set_fee_tiers(&mut self, tiers: &[FeeTier]) -> Result<()> {
+        // Higher volume bands must not get smaller discounts
+        require!(tiers.len() <= MAX_FEE_TIERS, MaxiFarmError::InvalidFeeTiers);
+        for (i, tier) in tiers.iter().enumerate() {
+            require!(tier.discount.le(&MAX_VOLUME_DISCOUNT), MaxiFarmError::InvalidFeeTiers);
+            if i > 0 {
+                require!(
+                    tier.min_volume > tiers[i - 1].min_volume && tier.discount >= tiers[i - 1].discount,
+                    MaxiFarmError::InvalidFeeTiers
+                );
+            }
+        }
+
+        self.num_fee_tiers = tiers.len() as u8;
+        self.fee_tiers = [FeeTier::default(); MAX_FEE_TIERS];
+        self.fee_tiers[..tiers.len()].copy_from_slice(tiers);
+        Ok(())
+    }
+
+    // This function applies volume-based fee tier discount
+    // Params
+    //   self - MainState struct itself
+    //   base_fee - Trading fee in effect for the pool
+    //   volume - Rolling SOL volume of the trader
+    // Return
+    //   Trading fee discounted by the highest tier reached
+    pub fn volume_discounted_fee(&self, base_fee: u64, volume: u64) -> u64 {
+        let discount = self.fee_tiers[..self.num_fee_tiers as usize]
+            .iter()
+            .rev()
+            .find(|tier| tier.min_volume <= volume)
+            .map_or(0, |tier| tier.discount);
+        base_fee - base_fee * discount / (100 * BPS)
+    }
+
+    // This function returns buyback vault's cut of a trading fee
+    // Params
+    //   self - MainState struct itself
+    //   fee - Trading fee charged
+    //   protocol_fee - Part of the fee left for protocol (after referral rewards and platform fee)
+    // Return
+    //   Buyback fee, limited to protocol's part
+    pub fn buyback_cut(&self, fee: u64, protocol_fee: u64) -> u64 {
+        (fee * self.buyback_share / (100 * BPS)).min(protocol_fee)
+    }
+
+    // This function returns trading fee applied to a trader
+    // Params
+    //   self - MainState struct itself
+    //   base_fee - Trading fee in effect for the pool
+    //   is_referee - Flag indicating whether the trader was referred
+    // Return
+    //   Trading fee (referee discount is applied for referred traders)
+    pub fn trading_fee_for(&self, base_fee: u64, is_referee: bool) -> u64 {
+        if is_referee {
+            return base_fee - base_fee * self.referee_discount / (100 * BPS);
+        }
+        base_fee
+    }
+
+    // This function records a trade in global counters
+    // Params
+    //   self - MainState struct itself
+    //   volume - Traded amount of SOL
+    //   fee - Trading fee charged
+    //   rewards - Referral rewards credited
+    pub fn record_trade(&mut self, volume: u64, fee: u64, rewards: u64) {
+        self.total_quote_volume += volume;
+        self.total_fees_collected += fee;
+        self.total_rewards_accrued += rewards;
+    }
 }
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+// Platform registered event
+#[event]
+pub struct PlatformRegisteredEvent {
+    pub admin: Pubkey, // Owner or fee manager
+    pub platform: Pubkey, // Platform's wallet address
+    pub fee_share: u64, // Share of trading fee
+    pub timestamp: i64 // Registered time
+}
+
+// Platform fee share updated event
+#[event]
+pub struct PlatformFeeUpdatedEvent {
+    pub admin: Pubkey, // Owner or fee manager
+    pub platform: Pubkey, // Platform's wallet address
+    pub fee_share: u64, // New share of trading fee
+    pub timestamp: i64 // Updated time
+}
+
+// Platform fees claimed event
+#[event]
+pub struct PlatformFeesClaimEvent {
+    pub platform: Pubkey, // Platform's wallet address
+    pub fees: u64, // Claimed amount
+    pub timestamp: i64 // Claimed time
+}
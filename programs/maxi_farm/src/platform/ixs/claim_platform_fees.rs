@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::{
+    error::MaxiFarmError,
+    utils::transfer_lamports,
+    MainState, PlatformState,
+    PlatformFeesClaimEvent,
+};
+
+// This function claims earned fees of a platform from main_state
+// Params
+//   ctx - ClaimPlatformFees context
+// Return
+//   Ok on success, ErrorCode on failure
+//     PlatformFeesClaimEvent is emitted on success
+pub fn claim_platform_fees(ctx: Context<AClaimPlatformFees>) -> Result<()> {
+    let platform_account = &mut ctx.accounts.platform_account;
+    let fee_amount = platform_account.earned_fees;
+    require!(fee_amount > 0, MaxiFarmError::NoRewardsAvailable);
+
+    // Transfer earned_fees (SOL) from main_state to platform
+    transfer_lamports(&ctx.accounts.main_state.to_account_info(), &ctx.accounts.authority.to_account_info(), fee_amount)?;
+
+    platform_account.earned_fees = 0;
+    platform_account.total_claimed += fee_amount;
+
+    emit!(PlatformFeesClaimEvent {
+        platform: platform_account.authority,
+        fees: fee_amount,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AClaimPlatformFees<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>, // Platform's wallet address
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>>, // MainState account
+
+    #[account(
+        mut,
+        seeds = [PlatformState::PREFIX_SEED, authority.key().as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub platform_account: Account<'info, PlatformState>, // Platform's PlatformState
+
+    pub system_program: Program<'info, System>
+}
@@ -0,0 +1,8 @@
+pub mod register_platform;
+pub use register_platform::*;
+
+pub mod set_platform_fee;
+pub use set_platform_fee::*;
+
+pub mod claim_platform_fees;
+pub use claim_platform_fees::*;
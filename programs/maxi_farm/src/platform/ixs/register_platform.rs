@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::MAX_PLATFORM_FEE_SHARE,
+    error::MaxiFarmError,
+    MainState, PlatformState,
+    PlatformRegisteredEvent,
+};
+
+// This function registers a platform (frontend/bot) routing trades
+// Params
+//   ctx - RegisterPlatform context
+//   platform - Platform's wallet address
+//   fee_share - Share of trading fee credited to the platform
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn register_platform(
+    ctx: Context<ARegisterPlatform>,
+    platform: Pubkey,
+    fee_share: u64
+) -> Result<()> {
+    require!(fee_share.le(&MAX_PLATFORM_FEE_SHARE), MaxiFarmError::InvalidPlatformFee);
+
+    let cur_timestamp = Clock::get()?.unix_timestamp;
+    let platform_account = &mut ctx.accounts.platform_account;
+    platform_account.authority = platform;
+    platform_account.fee_share = fee_share;
+    platform_account.registered_time = cur_timestamp as u64;
+
+    emit!(PlatformRegisteredEvent {
+        admin: ctx.accounts.admin.key(),
+        platform,
+        fee_share,
+        timestamp: cur_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(platform: Pubkey)]
+pub struct ARegisterPlatform<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>, // Owner or fee manager
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+        constraint = admin.key() == main_state.owner || admin.key() == main_state.fee_manager @ MaxiFarmError::Unauthorised
+    )]
+    pub main_state: Box<Account<'info, MainState>>, // MainState account
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [PlatformState::PREFIX_SEED, platform.as_ref()],
+        bump,
+        space = 8 + PlatformState::MAX_SIZE
+    )]
+    pub platform_account: Account<'info, PlatformState>, // Platform's PlatformState
+
+    pub system_program: Program<'info, System>
+}
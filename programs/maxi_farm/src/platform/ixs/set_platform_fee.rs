@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::MAX_PLATFORM_FEE_SHARE,
+    error::MaxiFarmError,
+    MainState, PlatformState,
+    PlatformFeeUpdatedEvent,
+};
+
+// This function sets fee share of a platform
+// Params
+//   ctx - SetPlatformFee context
+//   fee_share - New share of trading fee (0 to stop crediting the platform)
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn set_platform_fee(ctx: Context<ASetPlatformFee>, fee_share: u64) -> Result<()> {
+    require!(fee_share.le(&MAX_PLATFORM_FEE_SHARE), MaxiFarmError::InvalidPlatformFee);
+
+    let platform_account = &mut ctx.accounts.platform_account;
+    platform_account.fee_share = fee_share;
+
+    emit!(PlatformFeeUpdatedEvent {
+        admin: ctx.accounts.admin.key(),
+        platform: platform_account.authority,
+        fee_share,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetPlatformFee<'info> {
+    pub admin: Signer<'info>, // Owner or fee manager
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+        constraint = admin.key() == main_state.owner || admin.key() == main_state.fee_manager @ MaxiFarmError::Unauthorised
+    )]
+    pub main_state: Box<Account<'info, MainState>>, // MainState account
+
+    #[account(
+        mut,
+        seeds = [PlatformState::PREFIX_SEED, platform_account.authority.as_ref()],
+        bump
+    )]
+    pub platform_account: Account<'info, PlatformState>, // Platform's PlatformState
+}
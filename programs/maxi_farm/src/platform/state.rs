@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::constants::BPS;
+
+// Platform (frontend/bot) state
+#[account]
+pub struct PlatformState {
+    pub authority: Pubkey, // Platform's wallet address (claims fees)
+    pub fee_share: u64, // Share of trading fee credited to the platform (100 = 1%)
+    pub earned_fees: u64, // Accumulated (claimable) fees
+    pub total_earned: u64, // Lifetime earned fees
+    pub total_claimed: u64, // Lifetime claimed fees
+    pub registered_time: u64, // Registered time
+}
+
+impl PlatformState {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();    // Size of PlatformState
+    pub const PREFIX_SEED: &'static [u8] = b"platform";         // Seed of PlatformState
+
+    // This function returns platform's cut of a trading fee
+    // Params
+    //   self - PlatformState struct itself
+    //   fee - Trading fee charged
+    //   protocol_fee - Part of the fee left for protocol (after referral rewards)
+    // Return
+    //   Platform fee, limited to protocol's part
+    pub fn fee_cut(&self, fee: u64, protocol_fee: u64) -> u64 {
+        (fee * self.fee_share / (100 * BPS)).min(protocol_fee)
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use solana_program::keccak;
+use crate::error::MaxiFarmError;
+
+// Merkle proof of an allowlisted wallet
+//   Leaf is keccak256(wallet || allocation as u64 LE), and pairs are hashed in sorted order
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone)]
+pub struct AllowlistProof {
+    pub allocation: u64,            // Max. amount of SOL the wallet can buy with during private sale
+    pub proof: Vec<[u8; 32]>        // Sibling hashes from leaf to root
+}
+
+// This function verifies a wallet is in the allowlist
+// Params
+//   root - Merkle root of the allowlist
+//   trader - Wallet address
+//   proof - Merkle proof
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn verify_allowlist_proof(root: &[u8; 32], trader: &Pubkey, proof: &AllowlistProof) -> Result<()> {
+    require!(*root != [0; 32], MaxiFarmError::InvalidAllowlistProof);
+
+    let mut node = keccak::hashv(&[trader.as_ref(), &proof.allocation.to_le_bytes()]).0;
+    for sibling in proof.proof.iter() {
+        node = if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).0
+        } else {
+            keccak::hashv(&[sibling, &node]).0
+        };
+    }
+    require!(node == *root, MaxiFarmError::InvalidAllowlistProof);
+
+    Ok(())
+}
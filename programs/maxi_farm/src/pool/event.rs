@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::{PrivSalePhase, TaxSchedule, TokenGate};
 
 // BondingCurve create event
 #[event]
@@ -6,13 +7,19 @@ pub struct CreateEvent {
     pub creator: Pubkey,        // Creator wallet address
     pub base_mint: Pubkey,      // Creating token mint address
     pub metadata_uri: String,   // Metadata URI
-    pub tax: u64,               // Tax
+    pub buy_tax: u64,           // Tax on buys
+    pub sell_tax: u64,          // Tax on sells
     pub max_fee_tokens: u64,    // Max. fee tokens
     pub total_supply: u64,      // Total supply
+    pub curve_supply: u64,      // Supply put into the bonding curve
+    pub creator_allocation: u64, // Supply reserved for the creator in vesting escrow
     pub real_quote_threshold: u64,  // Real quote threshold
     pub base_reserves: u64,     // Number of total token reserves
     pub quote_reserves: u64,    // Number of total SOL reserves
     pub priv_sale_period: u64,  // Private sale period
+    pub start_time: u64,        // Trading start time
+    pub tax_schedule: TaxSchedule, // Launch tax schedule
+    pub allowlist_root: [u8; 32], // Merkle root of private sale allowlist (zero if not set)
     pub timestamp: i64,         // Creation time
     pub coin_type: u8           // Coin type
 }
@@ -34,13 +41,112 @@ pub struct TradeEvent {
     pub tier2_reward: u64,      // Tier2 reward
     pub tier3_referrer: Pubkey, // Tier3 referrer
     pub tier3_reward: u64,      // Tier3 reward
+    pub trading_fee: u64,       // Trading fee (SOL) charged
+    pub fee_discount: u64,      // Trading fee discounted for referred trader
+    pub priv_sale_phase: Option<u8>, // Private sale phase (None for public trading)
+    pub price_premium: u64,     // SOL paid into the bonding curve as private sale premium
+    pub pool_tax: u64,          // Buy/sell tax (SOL) paid to pool owner
+    pub tax_rate: u64,          // Buy/sell tax rate in effect
+    pub platform: Option<Pubkey>, // Platform routing the trade
+    pub platform_fee: u64,      // Part of trading fee credited to the platform
 }
 
 // Tax updated event
 #[event]
 pub struct TaxUpdatedEvent {
     pub owner: Pubkey,          // Owner
-    pub tax: u64                // New tax
+    pub base_mint: Pubkey,      // Token mint address
+    pub buy_tax: u64,           // New buy tax
+    pub sell_tax: u64,          // New sell tax
+    pub tax_schedule: TaxSchedule // New launch tax schedule
+}
+
+// Tax authority renounced event
+#[event]
+pub struct TaxAuthorityRenouncedEvent {
+    pub owner: Pubkey,          // Pool owner
+    pub base_mint: Pubkey,      // Token mint address
+    pub buy_tax: u64,           // Frozen buy tax
+    pub sell_tax: u64,          // Frozen sell tax
+    pub tax_schedule: TaxSchedule, // Frozen launch tax schedule
+    pub timestamp: i64          // Renounced time
+}
+
+// Pool owner proposed event
+#[event]
+pub struct PoolOwnerProposedEvent {
+    pub owner: Pubkey,          // Current pool owner
+    pub base_mint: Pubkey,      // Token mint address
+    pub pending_owner: Pubkey   // Proposed new owner (zero: proposal cancelled)
+}
+
+// Pool ownership transferred event
+#[event]
+pub struct PoolOwnerTransferredEvent {
+    pub previous_owner: Pubkey, // Previous pool owner
+    pub new_owner: Pubkey,      // New pool owner
+    pub base_mint: Pubkey       // Token mint address
+}
+
+// Pool trading fee override updated event
+#[event]
+pub struct PoolTradingFeeUpdatedEvent {
+    pub authority: Pubkey,      // Fee manager
+    pub base_mint: Pubkey,      // Token mint address
+    pub trading_fee: Option<u64> // New trading fee override (None: MainState's trading fee)
+}
+
+// Pool token gate updated event
+#[event]
+pub struct PoolTokenGateUpdatedEvent {
+    pub authority: Pubkey,      // Pool owner or fee manager
+    pub base_mint: Pubkey,      // Token mint address
+    pub gate: TokenGate         // New token gate
+}
+
+// Allowlist root updated event
+#[event]
+pub struct AllowlistRootUpdatedEvent {
+    pub authority: Pubkey,      // Pool owner or MainState owner
+    pub base_mint: Pubkey,      // Token mint address
+    pub root: [u8; 32]          // New Merkle root
+}
+
+// Pool cancelled event
+#[event]
+pub struct PoolCancelledEvent {
+    pub owner: Pubkey,          // Pool owner
+    pub base_mint: Pubkey,      // Token mint address
+    pub base_amount: u64,       // Token amount returned to owner
+    pub creator_allocation: u64, // Creator allocation returned to beneficiary
+    pub timestamp: i64          // Cancelled time
+}
+
+// Withdrawn pool closed event
+#[event]
+pub struct PoolClosedEvent {
+    pub owner: Pubkey,          // Pool owner (received rent)
+    pub base_mint: Pubkey,      // Token mint address
+    pub burnt_amount: u64,      // Burnt dust token amount
+    pub timestamp: i64          // Closed time
+}
+
+// Vested creator allocation claimed event
+#[event]
+pub struct VestedClaimedEvent {
+    pub beneficiary: Pubkey,    // Creator wallet address
+    pub base_mint: Pubkey,      // Token mint address
+    pub amount: u64,            // Claimed amount of tokens
+    pub claimed_amount: u64,    // Total claimed amount of tokens
+    pub timestamp: i64          // Claimed time
+}
+
+// Private sale phases updated event
+#[event]
+pub struct PrivSalePhasesUpdatedEvent {
+    pub authority: Pubkey,      // Pool owner or MainState owner
+    pub base_mint: Pubkey,      // Token mint address
+    pub phases: Vec<PrivSalePhase> // New phases
 }
 
 // BondingCurve complete event
@@ -59,3 +165,13 @@ pub struct WithdrawEvent {
     pub quote_amount: u64,      // Withdrawn SOL amount
     pub timestamp: i64          // Completed time
 }
+
+// Buyback executed event
+#[event]
+pub struct BuybackEvent {
+    pub cranker: Pubkey,        // Executor of the buyback
+    pub base_mint: Pubkey,      // Bought back token mint address
+    pub sol_amount: u64,        // Spent SOL amount (incl. buy tax)
+    pub burnt_amount: u64,      // Burnt token amount
+    pub timestamp: i64          // Executed time
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::{Mint}
+};
+use crate::{
+    error::MaxiFarmError,
+    PoolState,
+    PoolOwnerTransferredEvent
+};
+
+// This function accepts ownership of BondingCurve proposed by the current owner
+// Params
+//   ctx - AcceptPoolOwner context
+// Return
+//   Ok on success, ErrorCode on Failure
+//     PoolOwnerTransferred event is emitted on success
+pub fn accept_pool_owner(ctx: Context<AAcceptPoolOwner>) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    require!(pool_state.renounced.eq(&false), MaxiFarmError::OwnerRenounced);
+
+    let previous_owner = pool_state.owner;
+
+    // Update owner
+    pool_state.owner = ctx.accounts.new_owner.key();
+    pool_state.pending_owner = Pubkey::default();
+
+    emit!(PoolOwnerTransferredEvent {
+        previous_owner,
+        new_owner: pool_state.owner,
+        base_mint: pool_state.base_mint
+    });
+
+    Ok(())
+}
+
+// AcceptPoolOwner context
+#[derive(Accounts)]
+pub struct AAcceptPoolOwner<'info> {
+    #[account(
+        constraint = new_owner.key() == pool_state.pending_owner @ MaxiFarmError::Unauthorised
+    )]
+    pub new_owner: Signer<'info>, // Proposed pool owner
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            base_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>, // PoolState account
+
+    #[account(address = pool_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>> // Token mint address
+}
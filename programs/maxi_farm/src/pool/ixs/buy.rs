@@ -6,44 +6,140 @@ use anchor_spl::{
 };
 use solana_program::{
     system_instruction,
-    instruction::Instruction,
-    ed25519_program::ID as ED25519_ID,
     program::invoke,
-    sysvar::instructions::{load_instruction_at_checked, ID as IX_ID}
+    sysvar::instructions::ID as IX_ID
 };
 use crate::{
-    constants::{FEE_PRE_DIV, BPS},
+    constants::{FEE_PRE_DIV, BPS, BUYBACK_VAULT_SEED},
     error::MaxiFarmError,
-    utils::{calculate_fee, calculate_total_amount, close_token_account, sync_native_amount},
-    ed25519::{verify_ed25519_ix, merge_values},
-    MainState, PoolState, ReferralState,
+    utils::{calculate_fee, calculate_total_amount, calculate_transfer_fee, calculate_pre_fee_amount, close_token_account, sync_native_amount},
+    priv_sale::{check_priv_sale_access, check_priv_sale_amount, PrivSaleAccounts, PrivSaleAuth},
+    trader_stats::{trader_volume, record_trader_volume},
+    token_gate::check_token_gates,
+    referral::{referral_reward_rate, distribute_referral_rewards, check_referee},
+    MainState, PoolState, ReferralState, PlatformState,
     TradeEvent, CompleteEvent
 };
 
+// Amounts of a buy
+pub struct BuyAmounts {
+    pub base_amount: u64,           // Amount of tokens to buy
+    pub tax: u64,                   // Transfer tax on tokens
+    pub fee: u64,                   // Trading fee
+    pub fee_discount: u64,          // Trading fee discounted for referred buyer
+    pub pool_tax: u64,              // Buy tax paid to pool owner
+    pub pool_tax_rate: u64,         // Buy tax rate in effect
+    pub input_quote_amount: u64,    // Amount of SOL to buy with (fee, tax and premium excluded)
+    pub premium: u64                // Private sale price premium paid into the bonding curve
+}
+
+impl BuyAmounts {
+    // This function returns total amount of SOL paid by buyer
+    pub fn quote_amount(&self) -> u64 {
+        self.fee + self.pool_tax + self.input_quote_amount + self.premium
+    }
+}
+
+// This function calculates amounts of buying tokens with specified amount of SOL
+// Params
+//   pool_state - PoolState to buy from
+//   base_mint - Token mint account (transfer fee is read from it)
+//   quote_amount - Amount of SOL to buy with (restricted by real SOL threshold)
+//   trading_fee - Trading fee applied to the buyer
+//   base_trading_fee - Trading fee before referee discount
+//   buy_tax - Buy tax in effect
+//   premium_rate - Private sale price premium
+// Return
+//   Amounts of the buy
+pub fn calculate_buy_from_exact_sol(
+    pool_state: &mut PoolState,
+    base_mint: &AccountInfo,
+    quote_amount: u64,
+    trading_fee: u64,
+    base_trading_fee: u64,
+    buy_tax: u64,
+    premium_rate: u64
+) -> Result<BuyAmounts> {
+    // if new real_quote_reserves exceeds threshold, restrict quote_amount
+    let mut _quote_amount = quote_amount;
+    let mut fee = calculate_fee(trading_fee, _quote_amount);
+    let mut pool_tax = calculate_fee(buy_tax, _quote_amount);
+    if (pool_state.real_quote_reserves + (_quote_amount - fee - pool_tax) > pool_state.real_quote_threshold) {
+        _quote_amount = calculate_total_amount(trading_fee + buy_tax, pool_state.real_quote_threshold - pool_state.real_quote_reserves);
+        fee = calculate_fee(trading_fee, _quote_amount);
+        pool_tax = calculate_fee(buy_tax, _quote_amount);
+    }
+    let fee_discount = calculate_fee(base_trading_fee, _quote_amount).saturating_sub(fee);
+
+    // Premium is paid into the bonding curve without receiving tokens for it
+    let premium = (_quote_amount - fee - pool_tax) * premium_rate / (100 * BPS + premium_rate);
+    let input_quote_amount = _quote_amount - fee - pool_tax - premium;
+    let base_amount = pool_state.compute_receivable_amount_on_buy(input_quote_amount);
+
+    Ok(BuyAmounts {
+        base_amount,
+        tax: calculate_transfer_fee(base_mint, base_amount)?,
+        fee,
+        fee_discount,
+        pool_tax,
+        pool_tax_rate: buy_tax,
+        input_quote_amount,
+        premium
+    })
+}
+
 // Internal buy function
 // Params
 //   ctx - Buy context
-//   base_amount - Amount of tokens to buy
-//   fee - Trading fee
-//   input_quote_amount - Amount of SOL to buy with (fee excluded)
+//   amounts - Amounts of the buy
+//   priv_sale_phase - Private sale phase (None for public trading)
 // Return
 //   Ok on success
 //     If successful, emits (Buy) TradeEvent
 //       And if reaches complete marketCap, emits CompleteEvent as well
-fn buy_finalize(ctx: Context<ABuy>, base_amount: u64, tax: u64, fee: u64, input_quote_amount: u64) -> Result<()> {
+fn buy_finalize(ctx: Context<ABuy>, amounts: BuyAmounts, priv_sale_phase: Option<u8>) -> Result<()> {
+    let BuyAmounts { base_amount, tax, fee, fee_discount, pool_tax, pool_tax_rate, input_quote_amount, premium } = amounts;
     let main_state = &mut ctx.accounts.main_state;
     let pool_state = &mut ctx.accounts.pool_state;
     let buyer = ctx.accounts.buyer.to_account_info();
     let buyer_base_ata = &ctx.accounts.buyer_base_ata;
     let token_program = ctx.accounts.token_program.to_account_info();
 
-    if ctx.accounts.tier1_referral.is_some() { // If referral is valid, divide the fee
+    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+    let reward_rate = referral_reward_rate(main_state, &ctx.accounts.tier1_referral, cur_timestamp);
+    // Referral rewards are based on the undiscounted fee, so the discount is taken from the protocol share
+    let referral_fee = (fee + fee_discount) * reward_rate / (100 * BPS);
+    let platform_fee = ctx.accounts.platform.as_ref().map_or(0, |platform| platform.fee_cut(fee, fee.saturating_sub(referral_fee)));
+    // Referral rewards and platform fee are held in main_state until claimed
+    let held_fee = referral_fee + platform_fee;
+    // Buyback vault must be passed while buyback is enabled
+    require!(main_state.buyback_share == 0 || ctx.accounts.buyback_vault.is_some(), MaxiFarmError::MissingBuybackVault);
+    let buyback_fee = main_state.buyback_cut(fee, fee.saturating_sub(held_fee));
+
+    if buyback_fee > 0 {
+        // Transfer buyback fee (SOL) from buyer to buyback vault
+        let buyback_vault = ctx.accounts.buyback_vault.as_ref().ok_or(MaxiFarmError::MissingBuybackVault)?;
+        invoke(
+            &system_instruction::transfer(
+                &ctx.accounts.buyer.key(),
+                &buyback_vault.key(),
+                buyback_fee
+            ),
+            &[
+                ctx.accounts.buyer.to_account_info(),
+                buyback_vault.to_account_info(),
+                ctx.accounts.system_program.to_account_info()
+            ]
+        )?;
+    }
+
+    if held_fee > 0 { // If referral or platform is valid, divide the fee
         // Transfer fee (SOL) from buyer to feeRecpient
         invoke(
             &system_instruction::transfer(
                 &ctx.accounts.buyer.key(),
                 &main_state.fee_recipient.key(),
-                fee * (100 * BPS - main_state.tier1_reward) / (100 * BPS)
+                fee.saturating_sub(held_fee + buyback_fee)
             ),
             &[
                 ctx.accounts.buyer.to_account_info(),
@@ -52,12 +148,12 @@ fn buy_finalize(ctx: Context<ABuy>, base_amount: u64, tax: u64, fee: u64, input_
             ]
         )?;
 
-        // Transfer reward fee (SOL) from buyer to main_state
+        // Transfer reward and platform fee (SOL) from buyer to main_state
         invoke(
             &system_instruction::transfer(
                 &ctx.accounts.buyer.key(),
                 &main_state.key(),
-                fee * main_state.tier1_reward / (100 * BPS)
+                held_fee
             ),
             &[
                 ctx.accounts.buyer.to_account_info(),
@@ -71,7 +167,7 @@ fn buy_finalize(ctx: Context<ABuy>, base_amount: u64, tax: u64, fee: u64, input_
             &system_instruction::transfer(
                 &ctx.accounts.buyer.key(),
                 &main_state.fee_recipient.key(),
-                fee
+                fee - buyback_fee
             ),
             &[
                 ctx.accounts.buyer.to_account_info(),
@@ -81,12 +177,28 @@ fn buy_finalize(ctx: Context<ABuy>, base_amount: u64, tax: u64, fee: u64, input_
         )?;
     }
     
-    // Transfer input_quote_amount (SOL) from buyer to pool
+    // Transfer buy tax (SOL) from buyer to pool owner
+    if pool_tax > 0 {
+        invoke(
+            &system_instruction::transfer(
+                &ctx.accounts.buyer.key(),
+                &pool_state.owner,
+                pool_tax
+            ),
+            &[
+                ctx.accounts.buyer.to_account_info(),
+                ctx.accounts.pool_owner.to_account_info(),
+                ctx.accounts.system_program.to_account_info()
+            ]
+        )?;
+    }
+
+    // Transfer input_quote_amount and premium (SOL) from buyer to pool
     invoke(
         &system_instruction::transfer(
             &ctx.accounts.buyer.key(),
             &pool_state.key(),
-            input_quote_amount
+            input_quote_amount + premium
         ),
         &[
             ctx.accounts.buyer.to_account_info(),
@@ -95,45 +207,30 @@ fn buy_finalize(ctx: Context<ABuy>, base_amount: u64, tax: u64, fee: u64, input_
         ]
     )?;
 
-    let mut tier1_referrer = Pubkey::default();
-    let mut tier1_reward = 0;
-    let mut tier2_referrer = Pubkey::default();
-    let mut tier2_reward = 0;
-    let mut tier3_referrer = Pubkey::default();
-    let mut tier3_reward = 0;
-    
-    if let Some(tier3_referral) = &mut ctx.accounts.tier3_referral {
-        tier3_referrer = tier3_referral.user.clone();
-        tier3_reward = fee * main_state.tier3_reward / (100 * BPS);
-        tier3_referral.earned_rewards += tier3_reward;
-
-        if let Some(tier2_referral) = &mut ctx.accounts.tier2_referral { // If tier2 referrer exists, divide rewards
-            tier2_referrer = tier2_referral.user.clone();
-            tier2_reward = fee * main_state.tier2_reward / (100 * BPS);
-            tier2_referral.earned_rewards += tier2_reward;
-        }
-        if let Some(tier1_referral) = &mut ctx.accounts.tier1_referral {
-            tier1_referrer = tier1_referral.user.clone();
-            tier1_reward = fee * (main_state.tier1_reward - main_state.tier2_reward - main_state.tier3_reward) / (100 * BPS);
-            tier1_referral.earned_rewards += tier1_reward;
-        }
-    } else if let Some(tier2_referral) = &mut ctx.accounts.tier2_referral { // If tier2 referrer exists, divide rewards
-        tier2_referrer = tier2_referral.user.clone();
-        tier2_reward = fee * main_state.tier2_reward / (100 * BPS);
-        tier2_referral.earned_rewards += tier2_reward;
-
-        if let Some(tier1_referral) = &mut ctx.accounts.tier1_referral {
-            tier1_referrer = tier1_referral.user.clone();
-            tier1_reward = fee * (main_state.tier1_reward - main_state.tier2_reward) / (100 * BPS);
-            tier1_referral.earned_rewards += tier1_reward;
-        }
-    } else {
-        if let Some(tier1_referral) = &mut ctx.accounts.tier1_referral {
-            tier1_referrer = tier1_referral.user.clone();
-            tier1_reward = fee * main_state.tier1_reward / (100 * BPS);
-            tier1_referral.earned_rewards += tier1_reward;
-        }
+    if let Some(platform) = &mut ctx.accounts.platform {
+        platform.earned_fees += platform_fee;
+        platform.total_earned += platform_fee;
     }
+    let platform = ctx.accounts.platform.as_ref().map(|platform| platform.authority);
+
+    record_trader_volume(
+        &ctx.accounts.trader_stats,
+        &buyer,
+        &ctx.accounts.system_program.to_account_info(),
+        cur_timestamp,
+        fee + pool_tax + input_quote_amount + premium
+    )?;
+
+    let rewards = distribute_referral_rewards(
+        main_state,
+        fee + fee_discount,
+        fee + pool_tax + input_quote_amount + premium,
+        &mut ctx.accounts.tier1_referral,
+        &mut ctx.accounts.tier2_referral,
+        &mut ctx.accounts.tier3_referral,
+        cur_timestamp
+    );
+    main_state.record_trade(fee + pool_tax + input_quote_amount + premium, fee, rewards.total());
     
     // Transfer (meme) tokens from pool to buyer
     let original_amount = ctx.accounts.buyer_base_ata.amount;
@@ -165,43 +262,60 @@ fn buy_finalize(ctx: Context<ABuy>, base_amount: u64, tax: u64, fee: u64, input_
         user: buyer.key(), 
         base_mint: pool_state.base_mint, 
         token_amount: base_amount, 
-        sol_amount: fee + input_quote_amount, 
+        sol_amount: fee + pool_tax + input_quote_amount + premium, 
         base_reserves: pool_state.real_base_reserves + pool_state.virt_base_reserves, 
         quote_reserves: pool_state.virt_quote_reserves + pool_state.real_quote_reserves, 
         is_buy: true, 
-        timestamp: Clock::get()?.unix_timestamp,
-        tier1_referrer: tier1_referrer,
-        tier1_reward: tier1_reward,
-        tier2_referrer: tier2_referrer,
-        tier2_reward: tier2_reward,
-        tier3_referrer: tier3_referrer,
-        tier3_reward: tier3_reward
+        timestamp: cur_timestamp as i64,
+        tier1_referrer: rewards.tier1_referrer,
+        tier1_reward: rewards.tier1_reward,
+        tier2_referrer: rewards.tier2_referrer,
+        tier2_reward: rewards.tier2_reward,
+        tier3_referrer: rewards.tier3_referrer,
+        tier3_reward: rewards.tier3_reward,
+        trading_fee: fee,
+        fee_discount,
+        priv_sale_phase,
+        price_premium: premium,
+        pool_tax,
+        tax_rate: pool_tax_rate,
+        platform,
+        platform_fee
     });
     emit_cpi!(TradeEvent {
         user: buyer.key(), 
         base_mint: pool_state.base_mint, 
         token_amount: base_amount, 
-        sol_amount: fee + input_quote_amount, 
+        sol_amount: fee + pool_tax + input_quote_amount + premium, 
         base_reserves: pool_state.real_base_reserves + pool_state.virt_base_reserves, 
         quote_reserves: pool_state.virt_quote_reserves + pool_state.real_quote_reserves, 
         is_buy: true, 
-        timestamp: Clock::get()?.unix_timestamp,
-        tier1_referrer: tier1_referrer,
-        tier1_reward: tier1_reward,
-        tier2_referrer: tier2_referrer,
-        tier2_reward: tier2_reward,
-        tier3_referrer: tier3_referrer,
-        tier3_reward: tier3_reward
+        timestamp: cur_timestamp as i64,
+        tier1_referrer: rewards.tier1_referrer,
+        tier1_reward: rewards.tier1_reward,
+        tier2_referrer: rewards.tier2_referrer,
+        tier2_reward: rewards.tier2_reward,
+        tier3_referrer: rewards.tier3_referrer,
+        tier3_reward: rewards.tier3_reward,
+        trading_fee: fee,
+        fee_discount,
+        priv_sale_phase,
+        price_premium: premium,
+        pool_tax,
+        tax_rate: pool_tax_rate,
+        platform,
+        platform_fee
     });
 
     // Check if bonding curve becomes complete
     if (pool_state.real_quote_reserves >= pool_state.real_quote_threshold) {
         pool_state.complete = true;
+        main_state.total_pools_completed += 1;
         
         // Emit CompleteEvent
         emit!(CompleteEvent {
             base_mint: pool_state.base_mint, 
-            timestamp: Clock::get()?.unix_timestamp,
+            timestamp: cur_timestamp as i64,
         });
     }
 
@@ -215,7 +329,7 @@ fn buy_finalize(ctx: Context<ABuy>, base_amount: u64, tax: u64, fee: u64, input_
 //   min_base_amount - Minimum amount of tokens to receive
 // Return
 //   Ok on success, ErrorCode on failure
-pub fn buy_tokens_from_exact_sol(ctx:Context<ABuy>, quote_amount: u64, min_base_amount: u64, tax_bps: u64, sig: Option<Vec<u8>>) -> Result<()> {
+pub fn buy_tokens_from_exact_sol(ctx:Context<ABuy>, quote_amount: u64, min_base_amount: u64, auth: Option<PrivSaleAuth>) -> Result<()> {
     require!(quote_amount.gt(&0), MaxiFarmError::WrongQuoteAmount); // quote_amount must be greater than 0
 
     let main_state = &ctx.accounts.main_state;
@@ -224,47 +338,49 @@ pub fn buy_tokens_from_exact_sol(ctx:Context<ABuy>, quote_amount: u64, min_base_
     require!(pool_state.complete.eq(&false), MaxiFarmError::BondingCurveComplete); // BondingCurve must not be complete
 
     let cur_timestamp = Clock::get()?.unix_timestamp as u64;
-    if cur_timestamp < pool_state.created_time + pool_state.priv_sale_period {  // If not elapsed priv. sale period
-        if let Some(sig) = sig.clone() {
-            let mut ix: Instruction = load_instruction_at_checked(0, &ctx.accounts.ix_sysvar.to_account_info())?;
-            // if registerUser instruction was added at first, load next
-            if ix.program_id != ED25519_ID {
-                ix = load_instruction_at_checked(1, &ctx.accounts.ix_sysvar.to_account_info())?;
-            }
-            let signer = main_state.signer;
-            let msg = pool_state.base_mint.key();
-    
-            // Check that ix is what we expect to have sent
-            verify_ed25519_ix(&ix, signer.as_ref(), &msg.as_ref(), &sig)?;
-    
-            msg!("Signature is valid!");
-        } else {
-            require!(false, MaxiFarmError::MissingSignature);
-        }
-    }
+    require!(cur_timestamp >= pool_state.trading_start_time(), MaxiFarmError::TradingNotStarted);
+    let gate_benefits = check_token_gates(
+        main_state,
+        pool_state,
+        &ctx.accounts.buyer.key(),
+        &ctx.accounts.gate_token_account,
+        &ctx.accounts.gate_metadata
+    )?;
+    let priv_sale_accounts = PrivSaleAccounts {
+        trader: ctx.accounts.buyer.to_account_info(),
+        permit_nonce: &ctx.accounts.permit_nonce,
+        priv_sale_allocation: &ctx.accounts.priv_sale_allocation,
+        ix_sysvar: &ctx.accounts.ix_sysvar,
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_gate: &gate_benefits
+    };
+    let priv_sale_grant = check_priv_sale_access(main_state, pool_state, &priv_sale_accounts, &auth, true, cur_timestamp)?;
 
     let buyer = ctx.accounts.buyer.to_account_info();
     let buyer_base_ata = &ctx.accounts.buyer_base_ata;
     let token_program = ctx.accounts.token_program.to_account_info();
     let system_program = ctx.accounts.system_program.to_account_info();
 
-    // if new real_quote_reserves exceeds threshold, restrict quote_amount
-    let mut _quote_amount = quote_amount;
-    let mut fee = calculate_fee(main_state.trading_fee, _quote_amount);
-    if (pool_state.real_quote_reserves + (_quote_amount - fee) > pool_state.real_quote_threshold) {
-        _quote_amount = calculate_total_amount(main_state.trading_fee, pool_state.real_quote_threshold - pool_state.real_quote_reserves);
-        fee = calculate_fee(main_state.trading_fee, _quote_amount);
-    }
-    
-    let input_quote_amount = _quote_amount - fee;
-    let output_base_amount = pool_state.compute_receivable_amount_on_buy(input_quote_amount);
-    let tax_fee = calculate_fee(tax_bps, output_base_amount);
-    require!(output_base_amount.checked_sub(tax_fee).unwrap() >= min_base_amount, MaxiFarmError::TooFewOutputTokens); // Check minimum amount
+    let is_referee = check_referee(&ctx.accounts.buyer_referral, &ctx.accounts.tier1_referral)?;
+    let base_fee = main_state.base_trading_fee(pool_state.trading_fee, pool_state.trading_start_time(), cur_timestamp);
+    let volume = trader_volume(&ctx.accounts.trader_stats, &ctx.accounts.buyer.key(), cur_timestamp)?;
+    let base_fee = volume.map_or(base_fee, |volume| main_state.volume_discounted_fee(base_fee, volume));
+    let base_fee = gate_benefits.discounted_fee(base_fee);
+    let trading_fee = main_state.trading_fee_for(base_fee, is_referee);
+
+    let premium_rate = priv_sale_grant.as_ref().map_or(0, |grant| grant.phase.price_premium);
+    let base_mint = ctx.accounts.base_mint.to_account_info();
+    let buy_tax = pool_state.buy_tax_at(cur_timestamp);
+    let amounts = calculate_buy_from_exact_sol(pool_state, &base_mint, quote_amount, trading_fee, base_fee, buy_tax, premium_rate)?;
+    check_priv_sale_amount(&priv_sale_grant, &pool_state.key(), &priv_sale_accounts, amounts.quote_amount(), true)?;
+    require!(amounts.base_amount.checked_sub(amounts.tax).unwrap() >= min_base_amount, MaxiFarmError::TooFewOutputTokens); // Check minimum amount
 
-    pool_state.real_quote_reserves += input_quote_amount; // Increase Real SOL
-    pool_state.real_base_reserves -= output_base_amount; // Decrease Real tokens
+    pool_state.real_quote_reserves += amounts.input_quote_amount + amounts.premium; // Increase Real SOL
+    pool_state.real_base_reserves -= amounts.base_amount; // Decrease Real tokens
+    pool_state.trade_count += 1;
 
-    buy_finalize(ctx, output_base_amount, tax_fee, fee, input_quote_amount)
+    let priv_sale_phase = priv_sale_grant.map(|grant| grant.phase_index);
+    buy_finalize(ctx, amounts, priv_sale_phase)
 }
 
 // This function buys specified amount tokens on the bonding curve (required SOL amount is calculated internally)
@@ -274,33 +390,33 @@ pub fn buy_tokens_from_exact_sol(ctx:Context<ABuy>, quote_amount: u64, min_base_
 //   max_quote_amount - Maximum amount of SOL allowed to spend
 // Return
 //   Ok on success, ErrorCode on failure
-pub fn buy_exact_tokens_from_sol(ctx:Context<ABuy>, base_amount: u64, max_quote_amount: u64, tax_bps: u64, sig: Option<Vec<u8>>) -> Result<()> {
+pub fn buy_exact_tokens_from_sol(ctx:Context<ABuy>, base_amount: u64, max_quote_amount: u64, auth: Option<PrivSaleAuth>) -> Result<()> {
     let main_state = &ctx.accounts.main_state;
     
     let pool_state = &mut ctx.accounts.pool_state;
     require!(pool_state.complete.eq(&false), MaxiFarmError::BondingCurveComplete); // BondingCurve must not be complete
 
     let cur_timestamp = Clock::get()?.unix_timestamp as u64;
-    if cur_timestamp < pool_state.created_time + pool_state.priv_sale_period {  // If not elapsed priv. sale period
-        if let Some(sig) = sig.clone() {
-            let mut ix: Instruction = load_instruction_at_checked(0, &ctx.accounts.ix_sysvar.to_account_info())?;
-            // if registerUser instruction was added at first, load next
-            if ix.program_id != ED25519_ID {
-                ix = load_instruction_at_checked(1, &ctx.accounts.ix_sysvar.to_account_info())?;
-            }
-            let signer = main_state.signer;
-            let msg = pool_state.base_mint.key();
-    
-            // Check that ix is what we expect to have sent
-            verify_ed25519_ix(&ix, signer.as_ref(), &msg.as_ref(), &sig)?;
-    
-            msg!("Signature is valid!");
-        } else {
-            require!(false, MaxiFarmError::MissingSignature);
-        }
-    }
+    require!(cur_timestamp >= pool_state.trading_start_time(), MaxiFarmError::TradingNotStarted);
+    let gate_benefits = check_token_gates(
+        main_state,
+        pool_state,
+        &ctx.accounts.buyer.key(),
+        &ctx.accounts.gate_token_account,
+        &ctx.accounts.gate_metadata
+    )?;
+    let priv_sale_accounts = PrivSaleAccounts {
+        trader: ctx.accounts.buyer.to_account_info(),
+        permit_nonce: &ctx.accounts.permit_nonce,
+        priv_sale_allocation: &ctx.accounts.priv_sale_allocation,
+        ix_sysvar: &ctx.accounts.ix_sysvar,
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_gate: &gate_benefits
+    };
+    let priv_sale_grant = check_priv_sale_access(main_state, pool_state, &priv_sale_accounts, &auth, true, cur_timestamp)?;
 
-    let total_base_amount = calculate_total_amount(tax_bps, base_amount);
+    let base_mint = ctx.accounts.base_mint.to_account_info();
+    let total_base_amount = calculate_pre_fee_amount(&base_mint, base_amount)?;
 
     // base_amount must be greater than 0 and less than real_base_reserves
     require!(total_base_amount.gt(&0) && total_base_amount.le(&pool_state.real_base_reserves), MaxiFarmError::WrongBaseAmount);
@@ -318,15 +434,41 @@ pub fn buy_exact_tokens_from_sol(ctx:Context<ABuy>, base_amount: u64, max_quote_
         input_base_amount = pool_state.compute_receivable_amount_on_buy(input_quote_amount);
     }
     
-    let total_quote_amount = calculate_total_amount(main_state.trading_fee, input_quote_amount);
-    let fee = calculate_fee(main_state.trading_fee, total_quote_amount);
+    let is_referee = check_referee(&ctx.accounts.buyer_referral, &ctx.accounts.tier1_referral)?;
+    let base_fee = main_state.base_trading_fee(pool_state.trading_fee, pool_state.trading_start_time(), cur_timestamp);
+    let volume = trader_volume(&ctx.accounts.trader_stats, &ctx.accounts.buyer.key(), cur_timestamp)?;
+    let base_fee = volume.map_or(base_fee, |volume| main_state.volume_discounted_fee(base_fee, volume));
+    let base_fee = gate_benefits.discounted_fee(base_fee);
+    let trading_fee = main_state.trading_fee_for(base_fee, is_referee);
+
+    // Premium is paid into the bonding curve on top of the price
+    let premium_rate = priv_sale_grant.as_ref().map_or(0, |grant| grant.phase.price_premium);
+    let premium = input_quote_amount * premium_rate / (100 * BPS);
+
+    let buy_tax = pool_state.buy_tax_at(cur_timestamp);
+    let total_quote_amount = calculate_total_amount(trading_fee + buy_tax, input_quote_amount + premium);
+    let fee = calculate_fee(trading_fee, total_quote_amount);
+    let pool_tax = calculate_fee(buy_tax, total_quote_amount);
+    let fee_discount = calculate_fee(base_fee, total_quote_amount).saturating_sub(fee);
     require!(total_quote_amount <= max_quote_amount, MaxiFarmError::TooMuchInputSol);
+    check_priv_sale_amount(&priv_sale_grant, &pool_state.key(), &priv_sale_accounts, total_quote_amount, true)?;
 
     pool_state.real_base_reserves -= input_base_amount; // Decrease Real Tokens
-    pool_state.real_quote_reserves += input_quote_amount; // Increase Real SOL
+    pool_state.real_quote_reserves += input_quote_amount + premium; // Increase Real SOL
+    pool_state.trade_count += 1;
     
-    let tax_fee = calculate_fee(tax_bps, input_base_amount);
-    buy_finalize(ctx, input_base_amount, tax_fee, fee, input_quote_amount)
+    let tax_fee = calculate_transfer_fee(&base_mint, input_base_amount)?;
+    let priv_sale_phase = priv_sale_grant.map(|grant| grant.phase_index);
+    buy_finalize(ctx, BuyAmounts {
+        base_amount: input_base_amount,
+        tax: tax_fee,
+        fee,
+        fee_discount,
+        pool_tax,
+        pool_tax_rate: buy_tax,
+        input_quote_amount,
+        premium
+    }, priv_sale_phase)
 }
 
 
@@ -361,6 +503,13 @@ pub struct ABuy<'info> {
     )]
     pub pool_state: Box<Account<'info, PoolState>>, // PoolState account
 
+    #[account(
+        mut,
+        address = pool_state.owner
+    )]
+    /// CHECK: receives buy tax
+    pub pool_owner: AccountInfo<'info>, // Pool owner
+
     #[account(address = pool_state.base_mint)]
     pub base_mint: Box<InterfaceAccount<'info, Mint>>, // Token account
     
@@ -381,6 +530,11 @@ pub struct ABuy<'info> {
     )]
     pub reserver_base_ata: Box<InterfaceAccount<'info, TokenAccount>>, // PoolState's Token ATA
 
+    #[account(
+        seeds = [ReferralState::PREFIX_SEED, buyer.key().as_ref()],
+        bump
+    )]
+    pub buyer_referral: Option<Box<Account<'info, ReferralState>>>, // Buyer's ReferralState
     #[account(mut)]
     pub tier1_referral: Option<Box<Account<'info, ReferralState>>>,
     #[account(mut)]
@@ -388,6 +542,34 @@ pub struct ABuy<'info> {
     #[account(mut)]
     pub tier3_referral: Option<Box<Account<'info, ReferralState>>>,
 
+    #[account(mut)]
+    /// CHECK: PermitNonce PDA of the permit, created on use
+    pub permit_nonce: Option<UncheckedAccount<'info>>, // PermitNonce account (private sale only)
+
+    #[account(mut)]
+    /// CHECK: PrivSaleAllocation PDA of the trader (or held NFT for collection gate access) in the active phase, created on first use
+    pub priv_sale_allocation: Option<UncheckedAccount<'info>>, // PrivSaleAllocation account (private sale only)
+
+    #[account(mut)]
+    /// CHECK: TraderStats PDA of the trader, created on first use
+    pub trader_stats: Option<UncheckedAccount<'info>>, // TraderStats account (None: no volume discount)
+
+    pub gate_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>, // Trader's holding of a gated token (None: no holder benefits)
+
+    /// CHECK: checked against the held mint on token gate checks
+    pub gate_metadata: Option<UncheckedAccount<'info>>, // Metaplex metadata of the held NFT (collection gates only)
+
+    #[account(mut)]
+    pub platform: Option<Box<Account<'info, PlatformState>>>, // Platform routing the trade (None: no platform fee)
+
+    #[account(
+        mut,
+        seeds = [BUYBACK_VAULT_SEED],
+        bump
+    )]
+    /// CHECK: system-owned PDA holding SOL for buybacks
+    pub buyback_vault: Option<UncheckedAccount<'info>>, // Buyback vault (required while buyback is enabled)
+
     /// CHECK: this should be checked by owner
     #[account(address = IX_ID)]
     pub ix_sysvar: AccountInfo<'info>,
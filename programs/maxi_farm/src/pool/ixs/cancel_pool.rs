@@ -0,0 +1,206 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::{self, CloseAccount, Token2022, TransferChecked},
+    token_interface::{Mint, TokenAccount},
+    associated_token::AssociatedToken
+};
+use crate::{
+    error::MaxiFarmError,
+    PoolState, CreatorVesting,
+    PoolCancelledEvent
+};
+
+// This function cancels a pool that got no trades
+//   Supply is returned to the owner, and pool accounts are closed
+//   Creator allocation doesn't follow pool ownership, so vesting escrow is returned to its beneficiary (creator)
+// Params
+//   ctx - CancelPool context
+// Return
+//   Ok on success, ErrorCode on failure
+//     PoolCancelled event is emitted on success
+pub fn cancel_pool(ctx: Context<ACancelPool>) -> Result<()> {
+    let pool_state = &ctx.accounts.pool_state;
+    let cur_timestamp = Clock::get()?.unix_timestamp;
+    // Reserves must be the initial ones too, as trade_count is zero for pools migrated from older layout
+    require!(
+        pool_state.trade_count.eq(&0) && pool_state.real_quote_reserves.eq(&0) && pool_state.complete.eq(&false),
+        MaxiFarmError::PoolAlreadyTraded
+    );
+
+    // Bonding curve started with all supply except creator allocation, whose vesting accounts must be passed if it exists
+    let creator_allocation = match (&ctx.accounts.creator_vesting, &ctx.accounts.vesting_base_ata) {
+        (Some(creator_vesting), Some(_)) => {
+            let beneficiary = ctx.accounts.beneficiary.as_ref().ok_or(MaxiFarmError::InvalidCreatorAllocation)?;
+            let beneficiary_base_ata = ctx.accounts.beneficiary_base_ata.as_ref().ok_or(MaxiFarmError::InvalidCreatorAllocation)?;
+            require!(
+                beneficiary.key().eq(&creator_vesting.beneficiary) && beneficiary_base_ata.owner.eq(&creator_vesting.beneficiary),
+                MaxiFarmError::InvalidCreatorAllocation
+            );
+            creator_vesting.total_amount
+        },
+        (None, None) => 0,
+        _ => return err!(MaxiFarmError::InvalidCreatorAllocation)
+    };
+    require!(
+        (pool_state.real_base_reserves as u128 + creator_allocation as u128).eq(&(ctx.accounts.base_mint.supply as u128)),
+        MaxiFarmError::PoolAlreadyTraded
+    );
+
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let pool_seeds: &[&[&[u8]]] = &[&[
+        PoolState::PREFIX_SEED,
+        pool_state.base_mint.as_ref(),
+        &[ctx.bumps.pool_state]
+    ]];
+
+    // Transfer (meme) tokens from pool to owner
+    let base_amount = ctx.accounts.reserver_base_ata.amount;
+    token_2022::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TransferChecked {
+                from: ctx.accounts.reserver_base_ata.to_account_info(),
+                mint: ctx.accounts.base_mint.to_account_info(),
+                to: ctx.accounts.owner_base_ata.to_account_info(),
+                authority: pool_state.to_account_info()
+            },
+            pool_seeds
+        ),
+        base_amount,
+        ctx.accounts.base_mint.decimals
+    )?;
+
+    // Close pool's token account, rent goes to owner
+    token_2022::close_account(CpiContext::new_with_signer(
+        token_program.clone(),
+        CloseAccount {
+            account: ctx.accounts.reserver_base_ata.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: pool_state.to_account_info()
+        },
+        pool_seeds
+    ))?;
+
+    // Return creator allocation from vesting escrow to beneficiary, and close it
+    let mut returned_allocation = 0;
+    if let (Some(creator_vesting), Some(vesting_base_ata), Some(beneficiary), Some(beneficiary_base_ata)) = (
+        &ctx.accounts.creator_vesting,
+        &ctx.accounts.vesting_base_ata,
+        &ctx.accounts.beneficiary,
+        &ctx.accounts.beneficiary_base_ata
+    ) {
+        let vesting_seeds: &[&[&[u8]]] = &[&[
+            CreatorVesting::PREFIX_SEED,
+            creator_vesting.base_mint.as_ref(),
+            &[ctx.bumps.creator_vesting]
+        ]];
+        token_2022::transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TransferChecked {
+                    from: vesting_base_ata.to_account_info(),
+                    mint: ctx.accounts.base_mint.to_account_info(),
+                    to: beneficiary_base_ata.to_account_info(),
+                    authority: creator_vesting.to_account_info()
+                },
+                vesting_seeds
+            ),
+            vesting_base_ata.amount,
+            ctx.accounts.base_mint.decimals
+        )?;
+        returned_allocation = vesting_base_ata.amount;
+
+        // Rent goes to beneficiary, who paid it on creation
+        token_2022::close_account(CpiContext::new_with_signer(
+            token_program,
+            CloseAccount {
+                account: vesting_base_ata.to_account_info(),
+                destination: beneficiary.to_account_info(),
+                authority: creator_vesting.to_account_info()
+            },
+            vesting_seeds
+        ))?;
+        creator_vesting.close(beneficiary.to_account_info())?;
+    }
+
+    emit!(PoolCancelledEvent {
+        owner: pool_state.owner,
+        base_mint: pool_state.base_mint,
+        base_amount,
+        creator_allocation: returned_allocation,
+        timestamp: cur_timestamp
+    });
+
+    Ok(())
+}
+
+// CancelPool context
+#[derive(Accounts)]
+pub struct ACancelPool<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>, // Pool owner
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            base_mint.key().as_ref()
+        ],
+        bump,
+        has_one = owner,
+        constraint = !pool_state.renounced @ MaxiFarmError::OwnerRenounced,
+        close = owner
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>, // PoolState account (closed)
+
+    #[account(address = pool_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>, // Token mint address
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = pool_state,
+        associated_token::token_program = token_program
+    )]
+    pub reserver_base_ata: Box<InterfaceAccount<'info, TokenAccount>>, // PoolState's Token ATA (closed)
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = base_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
+    )]
+    pub owner_base_ata: Box<InterfaceAccount<'info, TokenAccount>>, // Owner's Token ATA
+
+    #[account(
+        mut,
+        seeds = [
+            CreatorVesting::PREFIX_SEED,
+            base_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub creator_vesting: Option<Box<Account<'info, CreatorVesting>>>, // CreatorVesting account (closed, creator allocation only)
+
+    #[account(
+        mut,
+        token::mint = base_mint,
+        token::authority = creator_vesting
+    )]
+    pub vesting_base_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>, // Vesting escrow's Token account (closed, creator allocation only)
+
+    #[account(mut)]
+    /// CHECK: checked against creator_vesting's beneficiary
+    pub beneficiary: Option<UncheckedAccount<'info>>, // Creator (receives rent of vesting accounts, creator allocation only)
+
+    #[account(
+        mut,
+        token::mint = base_mint
+    )]
+    pub beneficiary_base_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>, // Creator's Token account (creator allocation only)
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>
+}
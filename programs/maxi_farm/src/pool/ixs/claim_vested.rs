@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::{self, Token2022, TransferChecked},
+    token_interface::{Mint, TokenAccount},
+    associated_token::AssociatedToken
+};
+use crate::{
+    error::MaxiFarmError,
+    CreatorVesting,
+    VestedClaimedEvent
+};
+
+// This function releases unlocked creator allocation from vesting escrow
+// Params
+//   ctx - ClaimVested context
+// Return
+//   Ok on success, ErrorCode on failure
+//     VestedClaimed event is emitted on success
+pub fn claim_vested(ctx: Context<AClaimVested>) -> Result<()> {
+    let creator_vesting = &mut ctx.accounts.creator_vesting;
+    let cur_timestamp = Clock::get()?.unix_timestamp;
+
+    let amount = creator_vesting.claimable_amount(cur_timestamp as u64);
+    require!(amount.gt(&0), MaxiFarmError::NothingToClaim);
+    creator_vesting.claimed_amount += amount;
+
+    // Transfer (meme) tokens from vesting escrow to beneficiary
+    token_2022::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.vesting_base_ata.to_account_info(),
+                mint: ctx.accounts.base_mint.to_account_info(),
+                to: ctx.accounts.beneficiary_base_ata.to_account_info(),
+                authority: creator_vesting.to_account_info()
+            },
+            &[&[
+                CreatorVesting::PREFIX_SEED,
+                creator_vesting.base_mint.as_ref(),
+                &[ctx.bumps.creator_vesting]
+            ]]
+        ),
+        amount,
+        ctx.accounts.base_mint.decimals
+    )?;
+
+    emit!(VestedClaimedEvent {
+        beneficiary: creator_vesting.beneficiary,
+        base_mint: creator_vesting.base_mint,
+        amount,
+        claimed_amount: creator_vesting.claimed_amount,
+        timestamp: cur_timestamp
+    });
+
+    Ok(())
+}
+
+// ClaimVested context
+#[derive(Accounts)]
+pub struct AClaimVested<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>, // Creator
+
+    #[account(
+        mut,
+        seeds = [
+            CreatorVesting::PREFIX_SEED,
+            base_mint.key().as_ref()
+        ],
+        bump,
+        has_one = beneficiary
+    )]
+    pub creator_vesting: Box<Account<'info, CreatorVesting>>, // CreatorVesting account
+
+    #[account(address = creator_vesting.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>, // Token mint address
+
+    #[account(
+        mut,
+        token::mint = base_mint,
+        token::authority = creator_vesting
+    )]
+    pub vesting_base_ata: Box<InterfaceAccount<'info, TokenAccount>>, // Vesting escrow's Token account
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = base_mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program
+    )]
+    pub beneficiary_base_ata: Box<InterfaceAccount<'info, TokenAccount>>, // Creator's Token ATA
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::{self, Burn, CloseAccount, Token2022},
+    token_interface::{Mint, TokenAccount}
+};
+use crate::{
+    error::MaxiFarmError,
+    MainState, PoolState,
+    PoolClosedEvent
+};
+
+// This function closes a withdrawn pool to reclaim rent
+//   Rent goes to the pool owner, who paid it on creation
+//   Withheld transfer fees of the pool's token account must be harvested to the mint beforehand
+// Params
+//   ctx - ClosePool context
+// Return
+//   Ok on success, ErrorCode on failure
+//     PoolClosed event is emitted on success
+pub fn close_pool(ctx: Context<AClosePool>) -> Result<()> {
+    let pool_state = &ctx.accounts.pool_state;
+    require!(pool_state.complete.eq(&true), MaxiFarmError::BondingCurveIncomplete); // BondingCurve must be complete
+    require!(
+        pool_state.real_base_reserves.eq(&0) && pool_state.real_quote_reserves.eq(&0),
+        MaxiFarmError::BondingCurveNotWithdrawn
+    );
+
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let pool_seeds: &[&[&[u8]]] = &[&[
+        PoolState::PREFIX_SEED,
+        pool_state.base_mint.as_ref(),
+        &[ctx.bumps.pool_state]
+    ]];
+
+    // Burn dust left in pool's token account
+    let burnt_amount = ctx.accounts.reserver_base_ata.amount;
+    if burnt_amount > 0 {
+        token_2022::burn(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Burn {
+                    mint: ctx.accounts.base_mint.to_account_info(),
+                    from: ctx.accounts.reserver_base_ata.to_account_info(),
+                    authority: pool_state.to_account_info()
+                },
+                pool_seeds
+            ),
+            burnt_amount
+        )?;
+    }
+
+    // Close pool's token account, rent goes to pool owner
+    token_2022::close_account(CpiContext::new_with_signer(
+        token_program,
+        CloseAccount {
+            account: ctx.accounts.reserver_base_ata.to_account_info(),
+            destination: ctx.accounts.pool_owner.to_account_info(),
+            authority: pool_state.to_account_info()
+        },
+        pool_seeds
+    ))?;
+
+    emit!(PoolClosedEvent {
+        owner: pool_state.owner,
+        base_mint: pool_state.base_mint,
+        burnt_amount,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+// ClosePool context
+#[derive(Accounts)]
+pub struct AClosePool<'info> {
+    #[account(
+        constraint = (authority.key() == pool_state.owner && !pool_state.renounced) || authority.key() == main_state.withdrawer @ MaxiFarmError::Unauthorised
+    )]
+    pub authority: Signer<'info>, // Pool owner (if not renounced) or withdrawer
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>>, // MainState account
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            base_mint.key().as_ref()
+        ],
+        bump,
+        close = pool_owner
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>, // PoolState account (closed)
+
+    #[account(mut, address = pool_state.owner)]
+    /// CHECK: receives rent, checked by address
+    pub pool_owner: UncheckedAccount<'info>, // Pool owner
+
+    #[account(mut, address = pool_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>, // Token mint address
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = pool_state,
+        associated_token::token_program = token_program
+    )]
+    pub reserver_base_ata: Box<InterfaceAccount<'info, TokenAccount>>, // PoolState's Token ATA (closed)
+
+    pub token_program: Program<'info, Token2022>
+}
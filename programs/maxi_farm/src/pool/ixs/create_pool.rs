@@ -4,34 +4,65 @@ use anchor_spl::{
     token_interface::{Mint, TokenAccount},
     associated_token::AssociatedToken
 };
+use solana_program::{
+    system_instruction,
+    program::invoke
+};
 use std::str::FromStr;
 use crate::{
-    constants::{NATIVE_MINT_2022_STR, FEE_PRE_DIV, BPS, MAX_TAX, MAX_FEE_BPS, MAX_PRIV_SALE_PERIOD, DEF_PRIV_SALE_PERIOD},
+    constants::{NATIVE_MINT_2022_STR, BUYBACK_VAULT_SEED, FEE_PRE_DIV, BPS, MAX_FEE_BPS, MAX_PRIV_SALE_PERIOD, DEF_PRIV_SALE_PERIOD, MAX_START_DELAY, MAX_CREATOR_ALLOCATION, MAX_TAX_INCREASE, TAX_UPDATE_COOLDOWN},
     error::MaxiFarmError,
     utils::{check_balance, sync_native_amount, calculate_fee},
-    MainState, PoolState,
-    CreateEvent
+    buy::calculate_buy_from_exact_sol,
+    priv_sale::use_priv_sale_allocation,
+    vesting::{init_creator_vesting, CreatorVesting, VestingSchedule},
+    MainState, PoolState, TaxSchedule,
+    CreateEvent, TradeEvent, CompleteEvent
 };
 
+// Creator's initial buy on pool creation
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy)]
+pub struct InitialBuy {
+    pub lamports: u64,              // Amount of SOL to buy with (fee included)
+    pub min_base_amount: u64        // Minimum amount of tokens to receive
+}
+
 /*** Note: Here, 'pool' means 'bonding curve' - they've got the same meaning ***/
 
 // This function creates a new pool
 // Params
 //   ctx - CreatePool context
 //   base_amount - Token amount to put in the bonding curve
+//   buy_tax - Tax on buys (limited by MainState's max_buy_tax)
+//   sell_tax - Tax on sells (limited by MainState's max_sell_tax)
+//   opt_start_time - Trading start time (None to start at once)
+//   initial_buy - Creator's initial buy (None to skip)
+//   vesting - Vesting schedule of creator allocation (None if all supply is put into the bonding curve)
+//   tax_schedule - Launch tax schedule (None for no launch tax)
+//   allowlist_root - Merkle root of private sale allowlist (None for no allowlist)
 // Return
 //   Ok on success, ErrorCode on Failure
 //     CreateEvent is emitted on success
-pub fn create_pool(ctx: Context<ACreatePool>, metadata_uri:String, tax_bps: u64, max_fee_tokens: u64, real_quote_threshold: u64, coin_type: u8, opt_priv_sale_period: Option<u64>) -> Result<()> {
+//       And if initial buy is requested, (Buy) TradeEvent is emitted as well
+#[allow(clippy::too_many_arguments)]
+pub fn create_pool(mut ctx: Context<ACreatePool>, metadata_uri:String, buy_tax: u64, sell_tax: u64, max_fee_tokens: u64, real_quote_threshold: u64, coin_type: u8, opt_priv_sale_period: Option<u64>, opt_start_time: Option<u64>, initial_buy: Option<InitialBuy>, vesting: Option<VestingSchedule>, tax_schedule: Option<TaxSchedule>, allowlist_root: Option<[u8; 32]>) -> Result<()> {
     // input parameters check
     require!(
-        tax_bps.le(&MAX_TAX),
+        buy_tax.le(&ctx.accounts.main_state.max_buy_tax) && sell_tax.le(&ctx.accounts.main_state.max_sell_tax),
         MaxiFarmError::InvalidTax
     );
     require!(real_quote_threshold > 0, MaxiFarmError::InvalidRealQuoteThreshold);
 
+    // Supply is split into the bonding curve and creator allocation
+    let total_supply = ctx.accounts.base_mint.supply;
     let base_amount: u64 = ctx.accounts.reserver_base_ata.amount;
-    require!(base_amount.eq(&ctx.accounts.base_mint.supply), MaxiFarmError::WrongBaseAmountOnCreation);
+    let creator_allocation = ctx.accounts.vesting_base_ata.as_ref().map_or(0, |ata| ata.amount);
+    require!((base_amount + creator_allocation).eq(&total_supply), MaxiFarmError::WrongBaseAmountOnCreation);
+    require!(
+        (creator_allocation as u128).le(&(total_supply as u128 * MAX_CREATOR_ALLOCATION as u128 / (100 * BPS) as u128))
+            && (creator_allocation > 0) == vesting.is_some(),
+        MaxiFarmError::InvalidCreatorAllocation
+    );
     require!(
         max_fee_tokens.le(&(base_amount * MAX_FEE_BPS / (100 * BPS))),
         MaxiFarmError::InvalidMaxFeeTokens
@@ -51,10 +82,18 @@ pub fn create_pool(ctx: Context<ACreatePool>, metadata_uri:String, tax_bps: u64,
     let creator = ctx.accounts.creator.to_account_info();
     let token_program = ctx.accounts.token_program.to_account_info();
     let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+
+    if let Some(start_time) = opt_start_time {
+        require!(
+            start_time.ge(&cur_timestamp) && start_time.le(&(cur_timestamp + MAX_START_DELAY)),
+            MaxiFarmError::InvalidStartTime
+        );
+    }
     
     // Initialize all members of pool_state
     pool_state.owner = creator.key(); // Creator's address
-    pool_state.tax = tax_bps; // Transfer tax
+    pool_state.buy_tax = buy_tax;
+    pool_state.sell_tax = sell_tax;
     pool_state.max_fee_tokens = max_fee_tokens;
     pool_state.base_mint = ctx.accounts.base_mint.key(); // Token mint address
     pool_state.real_base_reserves = base_amount; // Total supply of tokens is all put into the pool (except tax)
@@ -68,38 +107,244 @@ pub fn create_pool(ctx: Context<ACreatePool>, metadata_uri:String, tax_bps: u64,
     } else {
         pool_state.priv_sale_period = DEF_PRIV_SALE_PERIOD;
     }
+    pool_state.start_time = opt_start_time.unwrap_or(cur_timestamp);
+    // Root is locked at trading start, so pools trading at once can set it on creation only
+    pool_state.allowlist_root = allowlist_root.unwrap_or_default();
+    pool_state.max_tax_increase = MAX_TAX_INCREASE;
+    pool_state.tax_update_cooldown = TAX_UPDATE_COOLDOWN;
+    if let Some(tax_schedule) = tax_schedule {
+        // Launch tax stays at end rate after decay, so it's limited as buy/sell tax
+        tax_schedule.validate(main_state.max_buy_tax.min(main_state.max_sell_tax))?;
+        pool_state.tax_schedule = tax_schedule;
+    }
+
+    if let Some(schedule) = vesting {
+        let vesting_base_ata = ctx.accounts.vesting_base_ata.as_ref().ok_or(MaxiFarmError::InvalidCreatorAllocation)?;
+        let creator_vesting = ctx.accounts.creator_vesting.as_ref().ok_or(MaxiFarmError::InvalidCreatorAllocation)?;
+        // Escrowed tokens must be held by vesting PDA
+        require!(vesting_base_ata.owner.eq(&creator_vesting.key()), MaxiFarmError::InvalidCreatorAllocation);
+        init_creator_vesting(
+            &creator,
+            &creator_vesting.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &pool_state.base_mint,
+            creator_allocation,
+            pool_state.trading_start_time(),
+            schedule
+        )?;
+    }
     pool_state.complete = false;
+    main_state.total_pools_created += 1;
 
     // Emit createPool event
     emit!(CreateEvent {
         creator: pool_state.owner, 
-        tax: pool_state.tax, 
+        buy_tax: pool_state.buy_tax,
+        sell_tax: pool_state.sell_tax,
         max_fee_tokens: pool_state.max_fee_tokens, 
         base_mint: pool_state.base_mint, 
         metadata_uri: metadata_uri.clone(), 
-        total_supply: base_amount, 
+        total_supply,
+        curve_supply: base_amount,
+        creator_allocation, 
         real_quote_threshold: real_quote_threshold, 
         base_reserves: pool_state.real_base_reserves + pool_state.virt_base_reserves, // Token reserves is the sum of real token reserves and virtual token reserves
         quote_reserves: pool_state.virt_quote_reserves, // SOL reserves is equal to virtual SOL reserves
         priv_sale_period: pool_state.priv_sale_period,
+        start_time: pool_state.start_time,
+        tax_schedule: pool_state.tax_schedule,
+        allowlist_root: pool_state.allowlist_root,
         timestamp: cur_timestamp as i64,
         coin_type: coin_type
     });
     emit_cpi!(CreateEvent {
         creator: pool_state.owner, 
-        tax: pool_state.tax, 
+        buy_tax: pool_state.buy_tax,
+        sell_tax: pool_state.sell_tax,
         max_fee_tokens: pool_state.max_fee_tokens, 
         base_mint: pool_state.base_mint, 
         metadata_uri: metadata_uri, 
-        total_supply: base_amount, 
+        total_supply,
+        curve_supply: base_amount,
+        creator_allocation, 
         real_quote_threshold: real_quote_threshold, 
         base_reserves: pool_state.real_base_reserves + pool_state.virt_base_reserves, // Token reserves is the sum of real token reserves and virtual token reserves
         quote_reserves: pool_state.virt_quote_reserves, // SOL reserves is equal to virtual SOL reserves
         priv_sale_period: pool_state.priv_sale_period,
+        start_time: pool_state.start_time,
+        tax_schedule: pool_state.tax_schedule,
+        allowlist_root: pool_state.allowlist_root,
         timestamp: cur_timestamp as i64,
         coin_type: coin_type
     });
 
+    if let Some(initial_buy) = initial_buy {
+        creator_initial_buy(&mut ctx, initial_buy, cur_timestamp)?;
+    }
+
+    Ok(())
+}
+
+// This function buys tokens for the creator right after the pool is created
+//   Creator is not checked against allowlist or permits, only per-wallet cap of the phase applies
+// Params
+//   ctx - CreatePool context
+//   initial_buy - Creator's initial buy
+//   cur_timestamp - Current time
+// Return
+//   Ok on success, ErrorCode on failure
+//     If successful, emits (Buy) TradeEvent
+//       And if reaches complete marketCap, emits CompleteEvent as well
+fn creator_initial_buy(ctx: &mut Context<ACreatePool>, initial_buy: InitialBuy, cur_timestamp: u64) -> Result<()> {
+    require!(initial_buy.lamports.gt(&0), MaxiFarmError::WrongQuoteAmount);
+
+    let main_state = &mut ctx.accounts.main_state;
+    let pool_state = &mut ctx.accounts.pool_state;
+    let creator = ctx.accounts.creator.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let fee_recipient = ctx.accounts.fee_recipient.as_ref().ok_or(MaxiFarmError::MissingInitialBuyAccounts)?;
+    let creator_base_ata = ctx.accounts.creator_base_ata.as_mut().ok_or(MaxiFarmError::MissingInitialBuyAccounts)?;
+
+    // Creator buys in the phase active at trading start, at launch fee and launch tax of trading start
+    let start_time = pool_state.trading_start_time();
+    let phase = pool_state.priv_sale_phase(start_time);
+    let premium_rate = phase.map_or(0, |(_, phase)| phase.price_premium);
+    let trading_fee = main_state.base_trading_fee(pool_state.trading_fee, start_time, start_time);
+    let buy_tax = pool_state.buy_tax_at(start_time);
+    let base_mint = ctx.accounts.base_mint.to_account_info();
+    let amounts = calculate_buy_from_exact_sol(pool_state, &base_mint, initial_buy.lamports, trading_fee, trading_fee, buy_tax, premium_rate)?;
+    require!(amounts.base_amount - amounts.tax >= initial_buy.min_base_amount, MaxiFarmError::TooFewOutputTokens); // Check minimum amount
+    if let Some((phase_index, phase)) = phase {
+        if phase.wallet_cap > 0 {
+            use_priv_sale_allocation(
+                &pool_state.key(),
+                phase_index,
+                &creator.key(),
+                &creator,
+                &ctx.accounts.priv_sale_allocation,
+                &system_program,
+                phase.wallet_cap,
+                amounts.quote_amount()
+            )?;
+        }
+    }
+
+    pool_state.real_quote_reserves += amounts.input_quote_amount + amounts.premium; // Increase Real SOL
+    pool_state.real_base_reserves -= amounts.base_amount; // Decrease Real tokens
+    pool_state.trade_count += 1;
+    main_state.record_trade(amounts.quote_amount(), amounts.fee, 0);
+
+    // Buyback vault must be passed while buyback is enabled
+    require!(main_state.buyback_share == 0 || ctx.accounts.buyback_vault.is_some(), MaxiFarmError::MissingBuybackVault);
+    let buyback_fee = main_state.buyback_cut(amounts.fee, amounts.fee);
+    if buyback_fee > 0 {
+        // Transfer buyback fee (SOL) from creator to buyback vault
+        let buyback_vault = ctx.accounts.buyback_vault.as_ref().ok_or(MaxiFarmError::MissingBuybackVault)?;
+        invoke(
+            &system_instruction::transfer(&creator.key(), &buyback_vault.key(), buyback_fee),
+            &[creator.clone(), buyback_vault.to_account_info(), system_program.clone()]
+        )?;
+    }
+
+    // Transfer fee (SOL) from creator to feeRecipient
+    invoke(
+        &system_instruction::transfer(&creator.key(), &fee_recipient.key(), amounts.fee - buyback_fee),
+        &[creator.clone(), fee_recipient.to_account_info(), system_program.clone()]
+    )?;
+
+    // Buy tax is paid to pool owner, which is the creator itself, so it's not transferred
+
+    // Transfer input_quote_amount and premium (SOL) from creator to pool
+    invoke(
+        &system_instruction::transfer(&creator.key(), &pool_state.key(), amounts.input_quote_amount + amounts.premium),
+        &[creator.clone(), pool_state.to_account_info(), system_program.clone()]
+    )?;
+
+    // Transfer (meme) tokens from pool to creator
+    let original_amount = creator_base_ata.amount;
+    token_2022::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.reserver_base_ata.to_account_info(),
+                mint: ctx.accounts.base_mint.to_account_info(),
+                to: creator_base_ata.to_account_info(),
+                authority: pool_state.to_account_info()
+            },
+            &[&[
+                PoolState::PREFIX_SEED,
+                pool_state.base_mint.as_ref(),
+                &[ctx.bumps.pool_state]
+            ]]
+        ),
+        amounts.base_amount,
+        ctx.accounts.base_mint.decimals
+    )?;
+    creator_base_ata.reload()?;
+    require!(creator_base_ata.amount - original_amount == amounts.base_amount - amounts.tax, MaxiFarmError::InvalidTax);
+
+    // Emit (Buy) TradeEvent
+    emit!(TradeEvent {
+        user: creator.key(),
+        base_mint: pool_state.base_mint,
+        token_amount: amounts.base_amount,
+        sol_amount: amounts.quote_amount(),
+        base_reserves: pool_state.real_base_reserves + pool_state.virt_base_reserves,
+        quote_reserves: pool_state.virt_quote_reserves + pool_state.real_quote_reserves,
+        is_buy: true,
+        timestamp: cur_timestamp as i64,
+        tier1_referrer: Pubkey::default(),
+        tier1_reward: 0,
+        tier2_referrer: Pubkey::default(),
+        tier2_reward: 0,
+        tier3_referrer: Pubkey::default(),
+        tier3_reward: 0,
+        trading_fee: amounts.fee,
+        fee_discount: 0,
+        priv_sale_phase: phase.map(|(phase_index, _)| phase_index),
+        price_premium: amounts.premium,
+        pool_tax: amounts.pool_tax,
+        tax_rate: amounts.pool_tax_rate,
+        platform: None,
+        platform_fee: 0
+    });
+    emit_cpi!(TradeEvent {
+        user: creator.key(),
+        base_mint: pool_state.base_mint,
+        token_amount: amounts.base_amount,
+        sol_amount: amounts.quote_amount(),
+        base_reserves: pool_state.real_base_reserves + pool_state.virt_base_reserves,
+        quote_reserves: pool_state.virt_quote_reserves + pool_state.real_quote_reserves,
+        is_buy: true,
+        timestamp: cur_timestamp as i64,
+        tier1_referrer: Pubkey::default(),
+        tier1_reward: 0,
+        tier2_referrer: Pubkey::default(),
+        tier2_reward: 0,
+        tier3_referrer: Pubkey::default(),
+        tier3_reward: 0,
+        trading_fee: amounts.fee,
+        fee_discount: 0,
+        priv_sale_phase: phase.map(|(phase_index, _)| phase_index),
+        price_premium: amounts.premium,
+        pool_tax: amounts.pool_tax,
+        tax_rate: amounts.pool_tax_rate,
+        platform: None,
+        platform_fee: 0
+    });
+
+    // Check if bonding curve becomes complete
+    if (pool_state.real_quote_reserves >= pool_state.real_quote_threshold) {
+        pool_state.complete = true;
+        main_state.total_pools_completed += 1;
+
+        // Emit CompleteEvent
+        emit!(CompleteEvent {
+            base_mint: pool_state.base_mint,
+            timestamp: cur_timestamp as i64,
+        });
+    }
+
     Ok(())
 }
 
@@ -111,6 +356,7 @@ pub struct ACreatePool<'info> {
     pub creator: Signer<'info>, // Pool creator
 
     #[account(
+        mut,
         seeds = [MainState::PREFIX_SEED],
         bump
     )]
@@ -141,6 +387,40 @@ pub struct ACreatePool<'info> {
     )]
     pub reserver_base_ata: Box<InterfaceAccount<'info, TokenAccount>>, // PoolState's Token ATA
 
+    #[account(
+        mut,
+        address = main_state.fee_recipient
+    )]
+    /// CHECK: checked by address
+    pub fee_recipient: Option<UncheckedAccount<'info>>, // Fee recipient (initial buy only)
+
+    #[account(
+        mut,
+        seeds = [BUYBACK_VAULT_SEED],
+        bump
+    )]
+    /// CHECK: system-owned PDA holding SOL for buybacks
+    pub buyback_vault: Option<UncheckedAccount<'info>>, // Buyback vault (initial buy while buyback is enabled)
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program
+    )]
+    pub creator_base_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>, // Creator's Token ATA (initial buy only)
+
+    #[account(mut)]
+    /// CHECK: PrivSaleAllocation PDA of the creator in the first phase, created on first use
+    pub priv_sale_allocation: Option<UncheckedAccount<'info>>, // PrivSaleAllocation account (initial buy with wallet cap only)
+
+    #[account(mut)]
+    /// CHECK: CreatorVesting PDA, created on pool creation
+    pub creator_vesting: Option<UncheckedAccount<'info>>, // CreatorVesting account (creator allocation only)
+
+    #[account(token::mint = base_mint)]
+    pub vesting_base_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>, // Vesting escrow's Token account (creator allocation only)
+
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>
@@ -0,0 +1,267 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::{self, Burn, Token2022, TransferChecked},
+    token_interface::{Mint, TokenAccount},
+    associated_token::AssociatedToken
+};
+use solana_program::{
+    system_instruction,
+    program::invoke_signed
+};
+use crate::{
+    constants::{BPS, BUYBACK_VAULT_SEED},
+    error::MaxiFarmError,
+    buy::calculate_buy_from_exact_sol,
+    MainState, PoolState,
+    BuybackEvent, TradeEvent, CompleteEvent
+};
+
+// This function buys back the configured token with buyback vault's SOL and burns bought tokens (permissionless)
+//   Spent SOL is limited by buyback_max_price_impact of the reserves, so sandwiching a buyback gains little
+//   Trading fee is not charged, as it would be paid to the protocol itself
+// Params
+//   ctx - ExecuteBuyback context
+//   min_base_amount - Minimum amount of tokens to buy back (on top of the price impact limit)
+// Return
+//   Ok on success, ErrorCode on failure
+//     If successful, emits BuybackEvent and (Buy) TradeEvent
+//       And if reaches complete marketCap, emits CompleteEvent as well
+pub fn execute_buyback(ctx: Context<AExecuteBuyback>, min_base_amount: u64) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    let pool_state = &mut ctx.accounts.pool_state;
+    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+
+    // Buyback trades publicly only
+    require!(
+        pool_state.complete.eq(&false)
+            && cur_timestamp >= pool_state.trading_start_time()
+            && pool_state.priv_sale_phase(cur_timestamp).is_none(),
+        MaxiFarmError::BuybackUnavailable
+    );
+
+    // Vault keeps rent exempt minimum
+    let buyback_vault = ctx.accounts.buyback_vault.to_account_info();
+    let mut quote_amount = buyback_vault.lamports().saturating_sub(Rent::get()?.minimum_balance(0));
+    if main_state.buyback_max_amount > 0 {
+        quote_amount = quote_amount.min(main_state.buyback_max_amount);
+    }
+    // Average price paid exceeds the price before the buyback by input / SOL reserves
+    let quote_reserves = pool_state.virt_quote_reserves + pool_state.real_quote_reserves;
+    let max_impact_amount = quote_reserves as u128 * main_state.buyback_max_price_impact as u128 / (100 * BPS) as u128;
+    quote_amount = quote_amount.min(max_impact_amount as u64);
+    require!(quote_amount.gt(&0), MaxiFarmError::WrongQuoteAmount);
+
+    let base_mint = ctx.accounts.base_mint.to_account_info();
+    let buy_tax = pool_state.buy_tax_at(cur_timestamp);
+    let amounts = calculate_buy_from_exact_sol(pool_state, &base_mint, quote_amount, 0, 0, buy_tax, 0)?;
+    require!(amounts.base_amount - amounts.tax >= min_base_amount, MaxiFarmError::TooFewOutputTokens); // Check minimum amount
+
+    pool_state.real_quote_reserves += amounts.input_quote_amount; // Increase Real SOL
+    pool_state.real_base_reserves -= amounts.base_amount; // Decrease Real tokens
+    pool_state.trade_count += 1;
+    main_state.record_trade(amounts.quote_amount(), 0, 0);
+
+    let vault_seeds: &[&[u8]] = &[BUYBACK_VAULT_SEED, &[ctx.bumps.buyback_vault]];
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    // Transfer input_quote_amount (SOL) from buyback vault to pool
+    invoke_signed(
+        &system_instruction::transfer(buyback_vault.key, &pool_state.key(), amounts.input_quote_amount),
+        &[buyback_vault.clone(), pool_state.to_account_info(), system_program.clone()],
+        &[vault_seeds]
+    )?;
+
+    // Transfer buy tax (SOL) from buyback vault to pool owner
+    if amounts.pool_tax > 0 {
+        invoke_signed(
+            &system_instruction::transfer(buyback_vault.key, &pool_state.owner, amounts.pool_tax),
+            &[buyback_vault.clone(), ctx.accounts.pool_owner.to_account_info(), system_program.clone()],
+            &[vault_seeds]
+        )?;
+    }
+
+    // Transfer (meme) tokens from pool to buyback vault
+    let original_amount = ctx.accounts.vault_base_ata.amount;
+    token_2022::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.reserver_base_ata.to_account_info(),
+                mint: base_mint.clone(),
+                to: ctx.accounts.vault_base_ata.to_account_info(),
+                authority: pool_state.to_account_info()
+            },
+            &[&[
+                PoolState::PREFIX_SEED,
+                pool_state.base_mint.as_ref(),
+                &[ctx.bumps.pool_state]
+            ]]
+        ),
+        amounts.base_amount,
+        ctx.accounts.base_mint.decimals
+    )?;
+    ctx.accounts.vault_base_ata.reload()?;
+    let bought_amount = ctx.accounts.vault_base_ata.amount - original_amount;
+    require!(bought_amount == amounts.base_amount - amounts.tax, MaxiFarmError::InvalidTax);
+
+    // Burn bought tokens
+    token_2022::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: base_mint,
+                from: ctx.accounts.vault_base_ata.to_account_info(),
+                authority: buyback_vault.clone()
+            },
+            &[vault_seeds]
+        ),
+        bought_amount
+    )?;
+
+    // Emit BuybackEvent
+    emit!(BuybackEvent {
+        cranker: ctx.accounts.cranker.key(),
+        base_mint: pool_state.base_mint,
+        sol_amount: amounts.quote_amount(),
+        burnt_amount: bought_amount,
+        timestamp: cur_timestamp as i64
+    });
+    emit_cpi!(BuybackEvent {
+        cranker: ctx.accounts.cranker.key(),
+        base_mint: pool_state.base_mint,
+        sol_amount: amounts.quote_amount(),
+        burnt_amount: bought_amount,
+        timestamp: cur_timestamp as i64
+    });
+
+    // Emit (Buy) TradeEvent
+    emit!(TradeEvent {
+        user: buyback_vault.key(),
+        base_mint: pool_state.base_mint,
+        token_amount: amounts.base_amount,
+        sol_amount: amounts.quote_amount(),
+        base_reserves: pool_state.real_base_reserves + pool_state.virt_base_reserves,
+        quote_reserves: pool_state.virt_quote_reserves + pool_state.real_quote_reserves,
+        is_buy: true,
+        timestamp: cur_timestamp as i64,
+        tier1_referrer: Pubkey::default(),
+        tier1_reward: 0,
+        tier2_referrer: Pubkey::default(),
+        tier2_reward: 0,
+        tier3_referrer: Pubkey::default(),
+        tier3_reward: 0,
+        trading_fee: 0,
+        fee_discount: 0,
+        priv_sale_phase: None,
+        price_premium: 0,
+        pool_tax: amounts.pool_tax,
+        tax_rate: amounts.pool_tax_rate,
+        platform: None,
+        platform_fee: 0
+    });
+    emit_cpi!(TradeEvent {
+        user: buyback_vault.key(),
+        base_mint: pool_state.base_mint,
+        token_amount: amounts.base_amount,
+        sol_amount: amounts.quote_amount(),
+        base_reserves: pool_state.real_base_reserves + pool_state.virt_base_reserves,
+        quote_reserves: pool_state.virt_quote_reserves + pool_state.real_quote_reserves,
+        is_buy: true,
+        timestamp: cur_timestamp as i64,
+        tier1_referrer: Pubkey::default(),
+        tier1_reward: 0,
+        tier2_referrer: Pubkey::default(),
+        tier2_reward: 0,
+        tier3_referrer: Pubkey::default(),
+        tier3_reward: 0,
+        trading_fee: 0,
+        fee_discount: 0,
+        priv_sale_phase: None,
+        price_premium: 0,
+        pool_tax: amounts.pool_tax,
+        tax_rate: amounts.pool_tax_rate,
+        platform: None,
+        platform_fee: 0
+    });
+
+    // Check if bonding curve becomes complete
+    if (pool_state.real_quote_reserves >= pool_state.real_quote_threshold) {
+        pool_state.complete = true;
+        main_state.total_pools_completed += 1;
+
+        // Emit CompleteEvent
+        emit!(CompleteEvent {
+            base_mint: pool_state.base_mint,
+            timestamp: cur_timestamp as i64,
+        });
+    }
+
+    Ok(())
+}
+
+// ExecuteBuyback context
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AExecuteBuyback<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>, // Anyone (pays rent of vault's token account)
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>>, // MainState account
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            base_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>, // PoolState account of buyback token
+
+    #[account(
+        mut,
+        address = pool_state.owner
+    )]
+    /// CHECK: receives buy tax
+    pub pool_owner: AccountInfo<'info>, // Pool owner
+
+    #[account(
+        mut,
+        address = main_state.buyback_mint @ MaxiFarmError::BuybackUnavailable
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>, // Buyback token mint
+
+    #[account(
+        mut,
+        seeds = [BUYBACK_VAULT_SEED],
+        bump
+    )]
+    /// CHECK: system-owned PDA holding SOL for buybacks
+    pub buyback_vault: UncheckedAccount<'info>, // Buyback vault
+
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = base_mint,
+        associated_token::authority = buyback_vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_base_ata: Box<InterfaceAccount<'info, TokenAccount>>, // Buyback vault's Token ATA
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = pool_state,
+        associated_token::token_program = token_program
+    )]
+    pub reserver_base_ata: Box<InterfaceAccount<'info, TokenAccount>>, // PoolState's Token ATA
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>
+}
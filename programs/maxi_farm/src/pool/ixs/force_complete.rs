@@ -5,7 +5,7 @@ use anchor_spl::{
 use crate::{
     constants::{NORMAL_REAL_QUOTE_THRESHOLD},
     error::MaxiFarmError,
-    PoolState,
+    MainState, PoolState,
     CompleteEvent
 };
 
@@ -19,6 +19,7 @@ pub fn force_complete(ctx: Context<AForceComplete>) -> Result<()> {
     let pool_state = &mut ctx.accounts.pool_state;
 
     require!(pool_state.complete.eq(&false), MaxiFarmError::BondingCurveComplete); // BondingCurve must not be complete
+    require!(pool_state.renounced.eq(&false), MaxiFarmError::OwnerRenounced);
     // Check if bonding curve becomes complete
     if (pool_state.real_quote_threshold <= NORMAL_REAL_QUOTE_THRESHOLD) {
         require!(pool_state.real_quote_reserves >= pool_state.real_quote_threshold * 4 / 5, MaxiFarmError::InsufficientRealQuoteReserves);
@@ -27,6 +28,7 @@ pub fn force_complete(ctx: Context<AForceComplete>) -> Result<()> {
     }
     
     pool_state.complete = true;
+    ctx.accounts.main_state.total_pools_completed += 1;
     // Emit CompleteEvent
     emit!(CompleteEvent {
         base_mint: pool_state.base_mint, 
@@ -42,6 +44,13 @@ pub struct AForceComplete<'info> {
     #[account()]
     pub owner: Signer<'info>, // Pool owner
 
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>>, // MainState account
+
     #[account(
         mut,
         seeds = [
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::{Mint}
+};
+use crate::{
+    PoolState, PrivSalePhase
+};
+
+// Active private sale phase
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy)]
+pub struct PrivSalePhaseInfo {
+    pub index: u8,                  // Phase index
+    pub start_time: u64,            // Phase start time
+    pub end_time: u64,              // Phase end time
+    pub phase: PrivSalePhase        // Phase parameters
+}
+
+// This function returns private sale phase active now (view)
+// Params
+//   ctx - GetPrivSalePhase context
+// Return
+//   Active phase, None if public trading opened
+pub fn get_priv_sale_phase(ctx: Context<AGetPrivSalePhase>) -> Result<Option<PrivSalePhaseInfo>> {
+    let pool_state = &ctx.accounts.pool_state;
+    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+
+    Ok(pool_state.priv_sale_phase(cur_timestamp).map(|(index, phase)| {
+        let next = index as usize + 1;
+        let end_time = if next < pool_state.num_priv_sale_phases as usize {
+            pool_state.trading_start_time() + pool_state.priv_sale_phases[next].start_offset
+        } else {
+            pool_state.public_sale_time()
+        };
+        PrivSalePhaseInfo {
+            index,
+            start_time: pool_state.trading_start_time() + phase.start_offset,
+            end_time,
+            phase
+        }
+    }))
+}
+
+#[derive(Accounts)]
+pub struct AGetPrivSalePhase<'info> {
+    #[account(
+        seeds = [
+            PoolState::PREFIX_SEED,
+            base_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>, // PoolState account
+
+    #[account(address = pool_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>> // Token mint address
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::{
+    utils::realloc_account,
+    PoolState,
+};
+
+// This function migrates a pool account to the current layout
+// Params
+//   ctx - PoolState migration context
+//   base_mint - Token mint address of the pool
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn migrate_pool_state(ctx: Context<AMigratePoolState>, _base_mint: Pubkey) -> Result<()> {
+    let pool_info = ctx.accounts.pool_state.to_account_info();
+    let is_initial_layout = pool_info.data_len() == 8 + PoolState::INITIAL_SIZE;
+
+    // New members are zero-initialized
+    realloc_account(
+        pool_info.clone(),
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        8 + PoolState::MAX_SIZE
+    )?;
+
+    if is_initial_layout {
+        // Transfer tax was stored where buy tax is, and is read from the mint now
+        let mut data = pool_info.try_borrow_mut_data()?;
+        let mut pool_state = PoolState::try_deserialize(&mut &data[..])?;
+        pool_state.buy_tax = 0;
+        pool_state.try_serialize(&mut &mut data[..])?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(base_mint: Pubkey)]
+pub struct AMigratePoolState<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>, // Payer of additional rent
+
+    #[account(
+        mut,
+        seeds = [PoolState::PREFIX_SEED, base_mint.as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    /// CHECK: PoolState in the previous layout
+    pub pool_state: UncheckedAccount<'info>, // PoolState account
+
+    pub system_program: Program<'info, System>
+}
@@ -10,8 +10,47 @@ pub use sell::*;
 pub mod update_tax;
 pub use update_tax::*;
 
+pub mod renounce_tax_authority;
+pub use renounce_tax_authority::*;
+
+pub mod propose_pool_owner;
+pub use propose_pool_owner::*;
+
+pub mod accept_pool_owner;
+pub use accept_pool_owner::*;
+
+pub mod execute_buyback;
+pub use execute_buyback::*;
+
 pub mod force_complete;
 pub use force_complete::*;
 
 pub mod withdraw;
 pub use withdraw::*;
+
+pub mod set_allowlist_root;
+pub use set_allowlist_root::*;
+
+pub mod set_priv_sale_phases;
+pub use set_priv_sale_phases::*;
+
+pub mod set_pool_trading_fee;
+pub use set_pool_trading_fee::*;
+
+pub mod set_pool_token_gate;
+pub use set_pool_token_gate::*;
+
+pub mod get_priv_sale_phase;
+pub use get_priv_sale_phase::*;
+
+pub mod cancel_pool;
+pub use cancel_pool::*;
+
+pub mod close_pool;
+pub use close_pool::*;
+
+pub mod claim_vested;
+pub use claim_vested::*;
+
+pub mod migrate_pool_state;
+pub use migrate_pool_state::*;
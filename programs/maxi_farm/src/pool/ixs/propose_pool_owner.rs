@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::{Mint}
+};
+use crate::{
+    error::MaxiFarmError,
+    PoolState,
+    PoolOwnerProposedEvent
+};
+
+// This function proposes a new owner of BondingCurve (the new owner must accept to take over)
+// Params
+//   ctx - ProposePoolOwner context
+//   new_owner - Address of proposed owner (zero to cancel the proposal)
+// Return
+//   Ok on success, ErrorCode on Failure
+//     PoolOwnerProposed event is emitted on success
+pub fn propose_pool_owner(ctx: Context<AProposePoolOwner>, new_owner: Pubkey) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    require!(pool_state.renounced.eq(&false), MaxiFarmError::OwnerRenounced);
+    require!(pool_state.owner.ne(&new_owner), MaxiFarmError::AlreadyBecameOwner); // Don't need to transfer ownership to the same user
+
+    pool_state.pending_owner = new_owner;
+
+    emit!(PoolOwnerProposedEvent {
+        owner: pool_state.owner,
+        base_mint: pool_state.base_mint,
+        pending_owner: new_owner
+    });
+
+    Ok(())
+}
+
+// ProposePoolOwner context
+#[derive(Accounts)]
+pub struct AProposePoolOwner<'info> {
+    #[account()]
+    pub owner: Signer<'info>, // Current pool owner
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            base_mint.key().as_ref(),
+        ],
+        bump,
+        has_one = owner
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>, // PoolState account
+
+    #[account(address = pool_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>> // Token mint address
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::{Mint}
+};
+use crate::{
+    error::MaxiFarmError,
+    PoolState,
+    TaxAuthorityRenouncedEvent
+};
+
+// This function renounces tax authority of BondingCurve token permanently
+//   Taxes are frozen, and pool owner can't update allowlist, private sale phases, cancel, force-complete or close the pool any more
+// Params
+//   ctx - RenounceTaxAuthority context
+// Return
+//   Ok on success, ErrorCode on Failure
+//     TaxAuthorityRenounced event is emitted on success
+pub fn renounce_tax_authority(ctx: Context<ARenounceTaxAuthority>) -> Result<()> {
+    let owner = ctx.accounts.owner.to_account_info();
+    let pool_state = &mut ctx.accounts.pool_state;
+    require!(pool_state.renounced.eq(&false), MaxiFarmError::OwnerRenounced);
+
+    pool_state.renounced = true;
+
+    let cur_timestamp = Clock::get()?.unix_timestamp;
+    emit!(TaxAuthorityRenouncedEvent {
+        owner: owner.key(),
+        base_mint: pool_state.base_mint,
+        buy_tax: pool_state.buy_tax,
+        sell_tax: pool_state.sell_tax,
+        tax_schedule: pool_state.tax_schedule,
+        timestamp: cur_timestamp
+    });
+
+    Ok(())
+}
+
+// RenounceTaxAuthority context
+#[derive(Accounts)]
+pub struct ARenounceTaxAuthority<'info> {
+    #[account()]
+    pub owner: Signer<'info>, // Pool owner
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            base_mint.key().as_ref(),
+        ],
+        bump,
+        has_one = owner
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>, // PoolState account
+
+    #[account(address = pool_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>> // Token mint address
+}
@@ -5,16 +5,17 @@ use anchor_spl::{
     associated_token::AssociatedToken
 };
 use solana_program::{
-    instruction::Instruction,
-    ed25519_program::ID as ED25519_ID,
-    sysvar::instructions::{load_instruction_at_checked, ID as IX_ID}
+    sysvar::instructions::ID as IX_ID
 };
 use crate::{
-    constants::BPS,
+    constants::{BPS, BUYBACK_VAULT_SEED},
     error::MaxiFarmError,
-    utils::{calculate_fee, close_token_account, sync_native_amount, check_balance, transfer_lamports},
-    ed25519::{verify_ed25519_ix, merge_values},
-    MainState, PoolState, ReferralState,
+    utils::{calculate_fee, calculate_transfer_fee, close_token_account, sync_native_amount, check_balance, transfer_lamports},
+    priv_sale::{check_priv_sale_access, check_priv_sale_amount, PrivSaleAccounts, PrivSaleAuth},
+    trader_stats::{trader_volume, record_trader_volume},
+    token_gate::check_token_gates,
+    referral::{referral_reward_rate, distribute_referral_rewards, check_referee},
+    MainState, PoolState, ReferralState, PlatformState,
     TradeEvent
 };
 
@@ -25,7 +26,7 @@ use crate::{
 //   min_quote_amount - Minimum amount of SOL to receive
 // Return
 //   Ok on success, ErrorCode on failure
-pub fn sell(ctx:Context<ASell>, base_amount: u64, min_quote_amount: u64, tax_bps: u64, sig: Option<Vec<u8>>) -> Result<()> {
+pub fn sell(ctx:Context<ASell>, base_amount: u64, min_quote_amount: u64, auth: Option<PrivSaleAuth>) -> Result<()> {
     // base_amount must be greater than 0 and less than real_base_reserves
     require!(base_amount.gt(&0), MaxiFarmError::WrongBaseAmount);
 
@@ -35,45 +36,58 @@ pub fn sell(ctx:Context<ASell>, base_amount: u64, min_quote_amount: u64, tax_bps
     require!(pool_state.complete.eq(&false), MaxiFarmError::BondingCurveComplete); // BondingCurve must not be complete
 
     let cur_timestamp = Clock::get()?.unix_timestamp as u64;
-    if cur_timestamp < pool_state.created_time + pool_state.priv_sale_period {  // If not elapsed priv. sale period
-        if let Some(sig) = sig.clone() {
-            let mut ix: Instruction = load_instruction_at_checked(0, &ctx.accounts.ix_sysvar.to_account_info())?;
-            // if registerUser instruction was added at first, load next
-            if ix.program_id != ED25519_ID {
-                ix = load_instruction_at_checked(1, &ctx.accounts.ix_sysvar.to_account_info())?;
-            }
-            let signer = main_state.signer;
-            let msg = pool_state.base_mint.key();
-    
-            // Check that ix is what we expect to have sent
-            verify_ed25519_ix(&ix, signer.as_ref(), &msg.as_ref(), &sig)?;
-    
-            msg!("Signature is valid!");
-        } else {
-            require!(false, MaxiFarmError::MissingSignature);
-        }
-    }
+    require!(cur_timestamp >= pool_state.trading_start_time(), MaxiFarmError::TradingNotStarted);
+    let gate_benefits = check_token_gates(
+        main_state,
+        pool_state,
+        &ctx.accounts.seller.key(),
+        &ctx.accounts.gate_token_account,
+        &ctx.accounts.gate_metadata
+    )?;
+    let priv_sale_accounts = PrivSaleAccounts {
+        trader: ctx.accounts.seller.to_account_info(),
+        permit_nonce: &ctx.accounts.permit_nonce,
+        priv_sale_allocation: &ctx.accounts.priv_sale_allocation,
+        ix_sysvar: &ctx.accounts.ix_sysvar,
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_gate: &gate_benefits
+    };
+    let priv_sale_grant = check_priv_sale_access(main_state, pool_state, &priv_sale_accounts, &auth, false, cur_timestamp)?;
 
     let seller = ctx.accounts.seller.to_account_info();
     let seller_base_ata = &ctx.accounts.seller_base_ata;
     let token_program = ctx.accounts.token_program.to_account_info();
     
-    let tax_fee = calculate_fee(tax_bps, base_amount);
+    let tax_fee = calculate_transfer_fee(&ctx.accounts.base_mint.to_account_info(), base_amount)?;
     let input_base_amount = base_amount.checked_sub(tax_fee).unwrap();
     let mut _output_amount = pool_state.compute_receivable_amount_on_sell(input_base_amount);
     
-    let mut fee = calculate_fee(main_state.trading_fee, _output_amount);
-    let mut output_amount = _output_amount - fee;
+    let is_referee = check_referee(&ctx.accounts.seller_referral, &ctx.accounts.tier1_referral)?;
+    let base_fee = main_state.base_trading_fee(pool_state.trading_fee, pool_state.trading_start_time(), cur_timestamp);
+    let volume = trader_volume(&ctx.accounts.trader_stats, &seller.key(), cur_timestamp)?;
+    let base_fee = volume.map_or(base_fee, |volume| main_state.volume_discounted_fee(base_fee, volume));
+    let base_fee = gate_benefits.discounted_fee(base_fee);
+    let trading_fee = main_state.trading_fee_for(base_fee, is_referee);
+
+    let sell_tax = pool_state.sell_tax_at(cur_timestamp);
+    let mut fee = calculate_fee(trading_fee, _output_amount);
+    let mut pool_tax = calculate_fee(sell_tax, _output_amount);
+    let mut output_amount = _output_amount - fee - pool_tax;
 
     require!(output_amount >= min_quote_amount, MaxiFarmError::TooLowOuputSol);
 
     pool_state.real_base_reserves += input_base_amount; // Increase Real Tokens
     if _output_amount > pool_state.real_quote_reserves {
         _output_amount = pool_state.real_quote_reserves;
-        fee = calculate_fee(main_state.trading_fee, _output_amount);
-        output_amount = _output_amount - fee;
+        fee = calculate_fee(trading_fee, _output_amount);
+        pool_tax = calculate_fee(sell_tax, _output_amount);
+        output_amount = _output_amount - fee - pool_tax;
     }
     pool_state.real_quote_reserves -= _output_amount; // Decrease Real SOL
+    pool_state.trade_count += 1;
+    check_priv_sale_amount(&priv_sale_grant, &pool_state.key(), &priv_sale_accounts, _output_amount, false)?;
+    let fee_discount = calculate_fee(base_fee, _output_amount).saturating_sub(fee);
+    let priv_sale_phase = priv_sale_grant.map(|grant| grant.phase_index);
 
     // Transfer (meme) tokens from seller to pool
     let orginal_amount = ctx.accounts.reserver_base_ata.amount;
@@ -92,57 +106,58 @@ pub fn sell(ctx:Context<ASell>, base_amount: u64, min_quote_amount: u64, tax_bps
     let after_amount = ctx.accounts.reserver_base_ata.amount;
     require!(after_amount - orginal_amount == input_base_amount, MaxiFarmError::InvalidTax);
     
-    if ctx.accounts.tier1_referral.is_some() {
+    let reward_rate = referral_reward_rate(main_state, &ctx.accounts.tier1_referral, cur_timestamp);
+    // Referral rewards are based on the undiscounted fee, so the discount is taken from the protocol share
+    let referral_fee = (fee + fee_discount) * reward_rate / (100 * BPS);
+    let platform_fee = ctx.accounts.platform.as_ref().map_or(0, |platform| platform.fee_cut(fee, fee.saturating_sub(referral_fee)));
+    // Referral rewards and platform fee are held in main_state until claimed
+    let held_fee = referral_fee + platform_fee;
+    // Buyback vault must be passed while buyback is enabled
+    require!(main_state.buyback_share == 0 || ctx.accounts.buyback_vault.is_some(), MaxiFarmError::MissingBuybackVault);
+    let buyback_fee = main_state.buyback_cut(fee, fee.saturating_sub(held_fee));
+    if buyback_fee > 0 {
+        // Transfer buyback fee (SOL) from pool to buyback vault
+        let buyback_vault = ctx.accounts.buyback_vault.as_ref().ok_or(MaxiFarmError::MissingBuybackVault)?;
+        transfer_lamports(&pool_state.to_account_info(), &buyback_vault.to_account_info(), buyback_fee)?;
+    }
+    if held_fee > 0 {
         // Transfer fee (SOL) from pool to feeRecipient
-        transfer_lamports(&pool_state.to_account_info(), &ctx.accounts.fee_recipient, fee * (100 * BPS - main_state.tier1_reward) / (100 * BPS))?;
-        // Transfer reward fee (SOL) from pool to main_state
-        transfer_lamports(&pool_state.to_account_info(), &main_state.to_account_info(), fee * main_state.tier1_reward / (100 * BPS))?;
+        transfer_lamports(&pool_state.to_account_info(), &ctx.accounts.fee_recipient, fee.saturating_sub(held_fee + buyback_fee))?;
+        // Transfer reward and platform fee (SOL) from pool to main_state
+        transfer_lamports(&pool_state.to_account_info(), &main_state.to_account_info(), held_fee)?;
     } else {
         // Transfer fee (SOL) from pool to feeRecipient
-        transfer_lamports(&pool_state.to_account_info(), &ctx.accounts.fee_recipient, fee)?;
+        transfer_lamports(&pool_state.to_account_info(), &ctx.accounts.fee_recipient, fee - buyback_fee)?;
     }
+    // Transfer sell tax (SOL) from pool to pool owner
+    transfer_lamports(&pool_state.to_account_info(), &ctx.accounts.pool_owner, pool_tax)?;
     // Transfer output_amount (SOL) from pool to seller
     transfer_lamports(&pool_state.to_account_info(), &seller, output_amount)?;
 
-    let mut tier1_referrer = Pubkey::default();
-    let mut tier1_reward = 0;
-    let mut tier2_referrer = Pubkey::default();
-    let mut tier2_reward = 0;
-    let mut tier3_referrer = Pubkey::default();
-    let mut tier3_reward = 0;
-    
-    if let Some(tier3_referral) = &mut ctx.accounts.tier3_referral {
-        tier3_referrer = tier3_referral.user.clone();
-        tier3_reward = fee * main_state.tier3_reward / (100 * BPS);
-        tier3_referral.earned_rewards += tier3_reward;
-
-        if let Some(tier2_referral) = &mut ctx.accounts.tier2_referral { // If tier2 referrer exists, divide rewards
-            tier2_referrer = tier2_referral.user.clone();
-            tier2_reward = fee * main_state.tier2_reward / (100 * BPS);
-            tier2_referral.earned_rewards += tier2_reward;
-        }
-        if let Some(tier1_referral) = &mut ctx.accounts.tier1_referral {
-            tier1_referrer = tier1_referral.user.clone();
-            tier1_reward = fee * (main_state.tier1_reward - main_state.tier2_reward - main_state.tier3_reward) / (100 * BPS);
-            tier1_referral.earned_rewards += tier1_reward;
-        }
-    } else if let Some(tier2_referral) = &mut ctx.accounts.tier2_referral { // If tier2 referrer exists, divide rewards
-        tier2_referrer = tier2_referral.user.clone();
-        tier2_reward = fee * main_state.tier2_reward / (100 * BPS);
-        tier2_referral.earned_rewards += tier2_reward;
-
-        if let Some(tier1_referral) = &mut ctx.accounts.tier1_referral {
-            tier1_referrer = tier1_referral.user.clone();
-            tier1_reward = fee * (main_state.tier1_reward - main_state.tier2_reward) / (100 * BPS);
-            tier1_referral.earned_rewards += tier1_reward;
-        }
-    } else {
-        if let Some(tier1_referral) = &mut ctx.accounts.tier1_referral {
-            tier1_referrer = tier1_referral.user.clone();
-            tier1_reward = fee * main_state.tier1_reward / (100 * BPS);
-            tier1_referral.earned_rewards += tier1_reward;
-        }
+    if let Some(platform) = &mut ctx.accounts.platform {
+        platform.earned_fees += platform_fee;
+        platform.total_earned += platform_fee;
     }
+    let platform = ctx.accounts.platform.as_ref().map(|platform| platform.authority);
+
+    record_trader_volume(
+        &ctx.accounts.trader_stats,
+        &seller,
+        &ctx.accounts.system_program.to_account_info(),
+        cur_timestamp,
+        _output_amount
+    )?;
+
+    let rewards = distribute_referral_rewards(
+        main_state,
+        fee + fee_discount,
+        _output_amount,
+        &mut ctx.accounts.tier1_referral,
+        &mut ctx.accounts.tier2_referral,
+        &mut ctx.accounts.tier3_referral,
+        cur_timestamp
+    );
+    main_state.record_trade(_output_amount, fee, rewards.total());
 
     // Emit (Sell) TradeEvent
     emit!(TradeEvent {
@@ -153,13 +168,21 @@ pub fn sell(ctx:Context<ASell>, base_amount: u64, min_quote_amount: u64, tax_bps
         base_reserves: pool_state.real_base_reserves + pool_state.virt_base_reserves, 
         quote_reserves: pool_state.virt_quote_reserves + pool_state.real_quote_reserves, 
         is_buy: false, 
-        timestamp: Clock::get()?.unix_timestamp,
-        tier1_referrer: tier1_referrer,
-        tier1_reward: tier1_reward,
-        tier2_referrer: tier2_referrer,
-        tier2_reward: tier2_reward,
-        tier3_referrer: tier3_referrer,
-        tier3_reward: tier3_reward
+        timestamp: cur_timestamp as i64,
+        tier1_referrer: rewards.tier1_referrer,
+        tier1_reward: rewards.tier1_reward,
+        tier2_referrer: rewards.tier2_referrer,
+        tier2_reward: rewards.tier2_reward,
+        tier3_referrer: rewards.tier3_referrer,
+        tier3_reward: rewards.tier3_reward,
+        trading_fee: fee,
+        fee_discount,
+        priv_sale_phase,
+        price_premium: 0,
+        pool_tax,
+        tax_rate: sell_tax,
+        platform,
+        platform_fee
     });
     emit_cpi!(TradeEvent {
         user: seller.key(), 
@@ -169,13 +192,21 @@ pub fn sell(ctx:Context<ASell>, base_amount: u64, min_quote_amount: u64, tax_bps
         base_reserves: pool_state.real_base_reserves + pool_state.virt_base_reserves, 
         quote_reserves: pool_state.virt_quote_reserves + pool_state.real_quote_reserves, 
         is_buy: false, 
-        timestamp: Clock::get()?.unix_timestamp,
-        tier1_referrer: tier1_referrer,
-        tier1_reward: tier1_reward,
-        tier2_referrer: tier2_referrer,
-        tier2_reward: tier2_reward,
-        tier3_referrer: tier3_referrer,
-        tier3_reward: tier3_reward
+        timestamp: cur_timestamp as i64,
+        tier1_referrer: rewards.tier1_referrer,
+        tier1_reward: rewards.tier1_reward,
+        tier2_referrer: rewards.tier2_referrer,
+        tier2_reward: rewards.tier2_reward,
+        tier3_referrer: rewards.tier3_referrer,
+        tier3_reward: rewards.tier3_reward,
+        trading_fee: fee,
+        fee_discount,
+        priv_sale_phase,
+        price_premium: 0,
+        pool_tax,
+        tax_rate: sell_tax,
+        platform,
+        platform_fee
     });
 
     Ok(())
@@ -213,6 +244,13 @@ pub struct ASell<'info> {
     )]
     pub pool_state: Box<Account<'info, PoolState>>, // PoolState account
 
+    #[account(
+        mut,
+        address = pool_state.owner
+    )]
+    /// CHECK: receives sell tax
+    pub pool_owner: AccountInfo<'info>, // Pool owner
+
     #[account(address = pool_state.base_mint)]
     pub base_mint: Box<InterfaceAccount<'info, Mint>>, // Token account
     
@@ -233,6 +271,11 @@ pub struct ASell<'info> {
     )]
     pub reserver_base_ata: Box<InterfaceAccount<'info, TokenAccount>>, // PoolState's Token ATA
 
+    #[account(
+        seeds = [ReferralState::PREFIX_SEED, seller.key().as_ref()],
+        bump
+    )]
+    pub seller_referral: Option<Box<Account<'info, ReferralState>>>, // Seller's ReferralState
     #[account(mut)]
     pub tier1_referral: Option<Box<Account<'info, ReferralState>>>,
     #[account(mut)]
@@ -240,6 +283,34 @@ pub struct ASell<'info> {
     #[account(mut)]
     pub tier3_referral: Option<Box<Account<'info, ReferralState>>>,
 
+    #[account(mut)]
+    /// CHECK: PermitNonce PDA of the permit, created on use
+    pub permit_nonce: Option<UncheckedAccount<'info>>, // PermitNonce account (private sale only)
+
+    #[account(mut)]
+    /// CHECK: PrivSaleAllocation PDA of the trader (or held NFT for collection gate access) in the active phase, created on first use
+    pub priv_sale_allocation: Option<UncheckedAccount<'info>>, // PrivSaleAllocation account (private sale only)
+
+    #[account(mut)]
+    /// CHECK: TraderStats PDA of the trader, created on first use
+    pub trader_stats: Option<UncheckedAccount<'info>>, // TraderStats account (None: no volume discount)
+
+    pub gate_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>, // Trader's holding of a gated token (None: no holder benefits)
+
+    /// CHECK: checked against the held mint on token gate checks
+    pub gate_metadata: Option<UncheckedAccount<'info>>, // Metaplex metadata of the held NFT (collection gates only)
+
+    #[account(mut)]
+    pub platform: Option<Box<Account<'info, PlatformState>>>, // Platform routing the trade (None: no platform fee)
+
+    #[account(
+        mut,
+        seeds = [BUYBACK_VAULT_SEED],
+        bump
+    )]
+    /// CHECK: system-owned PDA holding SOL for buybacks
+    pub buyback_vault: Option<UncheckedAccount<'info>>, // Buyback vault (required while buyback is enabled)
+
     /// CHECK: this should be checked by owner
     #[account(address = IX_ID)]
     pub ix_sysvar: AccountInfo<'info>,
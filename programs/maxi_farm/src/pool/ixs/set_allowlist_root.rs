@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::{Mint}
+};
+use crate::{
+    error::MaxiFarmError,
+    MainState, PoolState,
+    AllowlistRootUpdatedEvent
+};
+
+// This function sets Merkle root of private sale allowlist
+// Params
+//   ctx - SetAllowlistRoot context
+//   root - New Merkle root (zero to disable allowlist)
+// Return
+//   Ok on success, ErrorCode on failure
+//     AllowlistRootUpdated event is emitted on success
+pub fn set_allowlist_root(ctx: Context<ASetAllowlistRoot>, root: [u8; 32]) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+
+    // Root can't be changed once (gated) trading started
+    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+    require!(!pool_state.priv_sale_locked(cur_timestamp), MaxiFarmError::TradingStarted);
+
+    pool_state.allowlist_root = root;
+
+    emit!(AllowlistRootUpdatedEvent {
+        authority: ctx.accounts.authority.key(),
+        base_mint: pool_state.base_mint,
+        root
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetAllowlistRoot<'info> {
+    #[account(
+        constraint = (authority.key() == pool_state.owner && !pool_state.renounced) || authority.key() == main_state.owner @ MaxiFarmError::Unauthorised
+    )]
+    pub authority: Signer<'info>, // Pool owner or MainState owner
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>>, // MainState account
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            base_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>, // PoolState account
+
+    #[account(address = pool_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>> // Token mint address
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::{Mint}
+};
+use crate::{
+    error::MaxiFarmError,
+    MainState, PoolState, TokenGate,
+    PoolTokenGateUpdatedEvent
+};
+
+// This function sets benefits for holders of a token on the pool
+//   Fee discounts cut protocol fee, so only fee manager can grant them
+// Params
+//   ctx - SetPoolTokenGate context
+//   gate - New token gate (zero key to disable)
+// Return
+//   Ok on success, ErrorCode on failure
+//     PoolTokenGateUpdated event is emitted on success
+pub fn set_pool_token_gate(ctx: Context<ASetPoolTokenGate>, gate: TokenGate) -> Result<()> {
+    gate.validate()?;
+    require!(
+        gate.fee_discount == 0 || ctx.accounts.authority.key() == ctx.accounts.main_state.fee_manager,
+        MaxiFarmError::Unauthorised
+    );
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    pool_state.token_gate = gate;
+
+    emit!(PoolTokenGateUpdatedEvent {
+        authority: ctx.accounts.authority.key(),
+        base_mint: pool_state.base_mint,
+        gate
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetPoolTokenGate<'info> {
+    #[account(
+        constraint = (authority.key() == pool_state.owner && !pool_state.renounced) || authority.key() == main_state.fee_manager @ MaxiFarmError::Unauthorised
+    )]
+    pub authority: Signer<'info>, // Pool owner or fee manager
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>>, // MainState account
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            base_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>, // PoolState account
+
+    #[account(address = pool_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>> // Token mint address
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::{Mint}
+};
+use crate::{
+    constants::MAX_TRADING_FEE,
+    error::MaxiFarmError,
+    MainState, PoolState,
+    PoolTradingFeeUpdatedEvent
+};
+
+// This function overrides trading fee of the pool
+// Params
+//   ctx - SetPoolTradingFee context
+//   trading_fee - New trading fee override (None to use MainState's trading fee)
+// Return
+//   Ok on success, ErrorCode on failure
+//     PoolTradingFeeUpdated event is emitted on success
+pub fn set_pool_trading_fee(ctx: Context<ASetPoolTradingFee>, trading_fee: Option<u64>) -> Result<()> {
+    if let Some(fee) = trading_fee {
+        require!(fee.le(&MAX_TRADING_FEE), MaxiFarmError::InvalidTradingFee);
+    }
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    pool_state.trading_fee = trading_fee;
+
+    emit!(PoolTradingFeeUpdatedEvent {
+        authority: ctx.accounts.authority.key(),
+        base_mint: pool_state.base_mint,
+        trading_fee
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetPoolTradingFee<'info> {
+    #[account(
+        constraint = authority.key() == main_state.fee_manager @ MaxiFarmError::Unauthorised
+    )]
+    pub authority: Signer<'info>, // Fee manager
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>>, // MainState account
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            base_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>, // PoolState account
+
+    #[account(address = pool_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>> // Token mint address
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_interface::{Mint}
+};
+use crate::{
+    error::MaxiFarmError,
+    MainState, PoolState, PrivSalePhase,
+    PrivSalePhasesUpdatedEvent
+};
+
+// This function sets private sale phases of the pool
+// Params
+//   ctx - SetPrivSalePhases context
+//   phases - Private sale phases, ordered by start offset (empty for a single phase)
+// Return
+//   Ok on success, ErrorCode on failure
+//     PrivSalePhasesUpdated event is emitted on success
+pub fn set_priv_sale_phases(ctx: Context<ASetPrivSalePhases>, phases: Vec<PrivSalePhase>) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+
+    // Phases can't be changed once (gated) trading started, as allocations are tracked by phase index
+    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+    require!(!pool_state.priv_sale_locked(cur_timestamp), MaxiFarmError::TradingStarted);
+
+    pool_state.set_priv_sale_phases(&phases)?;
+
+    emit!(PrivSalePhasesUpdatedEvent {
+        authority: ctx.accounts.authority.key(),
+        base_mint: pool_state.base_mint,
+        phases
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetPrivSalePhases<'info> {
+    #[account(
+        constraint = (authority.key() == pool_state.owner && !pool_state.renounced) || authority.key() == main_state.owner @ MaxiFarmError::Unauthorised
+    )]
+    pub authority: Signer<'info>, // Pool owner or MainState owner
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>>, // MainState account
+
+    #[account(
+        mut,
+        seeds = [
+            PoolState::PREFIX_SEED,
+            base_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>, // PoolState account
+
+    #[account(address = pool_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>> // Token mint address
+}
@@ -3,44 +3,85 @@ use anchor_spl::{
     token_interface::{Mint}
 };
 use crate::{
-    constants::{MAX_TAX},
     error::MaxiFarmError,
+    MainState,
     PoolState,
+    TaxSchedule,
     TaxUpdatedEvent
 };
 
-// This function updates tax of BondingCurve token.
+// This function updates buy/sell tax of BondingCurve token.
+//   Each update can raise a tax by pool's max_tax_increase at most, once per pool's tax_update_cooldown
+//   While launch tax decays, taxes can only be lowered
 // Params
 //   ctx - UpdateTax context
+//   buy_tax - New tax on buys (limited by MainState's max_buy_tax)
+//   sell_tax - New tax on sells (limited by MainState's max_sell_tax)
+//   tax_schedule - New launch tax schedule (can only be lowered, None to keep)
 // Return
 //   Ok on success, ErrorCode on Failure
 //     UpdateTax event is emitted on success
-pub fn update_tax(ctx: Context<AUpdateTax>, new_tax: u64) -> Result<()> {
+pub fn update_tax(ctx: Context<AUpdateTax>, buy_tax: u64, sell_tax: u64, tax_schedule: Option<TaxSchedule>) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+
     // input parameters check
     require!(
-        new_tax.le(&MAX_TAX),
+        buy_tax.le(&main_state.max_buy_tax) && sell_tax.le(&main_state.max_sell_tax),
         MaxiFarmError::InvalidTax
     );
 
     let owner = ctx.accounts.owner.to_account_info();
     let pool_state = &mut ctx.accounts.pool_state;
-    pool_state.tax = new_tax;
+    require!(pool_state.renounced.eq(&false), MaxiFarmError::OwnerRenounced);
+
+    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+    require!(
+        pool_state.last_tax_update == 0 || cur_timestamp >= pool_state.last_tax_update + pool_state.tax_update_cooldown,
+        MaxiFarmError::TaxUpdateTooSoon
+    );
+    // Tax applied during launch can only be lowered
+    require!(
+        !pool_state.tax_schedule_active(cur_timestamp) || (buy_tax <= pool_state.buy_tax && sell_tax <= pool_state.sell_tax),
+        MaxiFarmError::TaxScheduleActive
+    );
+    require!(
+        buy_tax <= pool_state.buy_tax + pool_state.max_tax_increase && sell_tax <= pool_state.sell_tax + pool_state.max_tax_increase,
+        MaxiFarmError::TaxChangeTooLarge
+    );
+
+    pool_state.buy_tax = buy_tax;
+    pool_state.sell_tax = sell_tax;
+    if let Some(tax_schedule) = tax_schedule {
+        tax_schedule.validate(main_state.max_buy_tax.min(main_state.max_sell_tax))?;
+        require!(tax_schedule.is_lower_than(&pool_state.tax_schedule), MaxiFarmError::TaxScheduleNotLowered);
+        pool_state.tax_schedule = tax_schedule;
+    }
+    pool_state.last_tax_update = cur_timestamp;
         
-    // Emit CompleteEvent
+    // Emit TaxUpdatedEvent
     emit!(TaxUpdatedEvent {
         owner: owner.key(),
-        tax: new_tax
+        base_mint: pool_state.base_mint,
+        buy_tax,
+        sell_tax,
+        tax_schedule: pool_state.tax_schedule
     });
 
     Ok(())
 }
 
-// CreatePool context
+// UpdateTax context
 #[derive(Accounts)]
 pub struct AUpdateTax<'info> {
     #[account()]
     pub owner: Signer<'info>, // Pool owner
 
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>>, // MainState account
+
     #[account(
         mut,
         seeds = [
@@ -50,7 +91,7 @@ pub struct AUpdateTax<'info> {
         bump,
         has_one = owner
     )]
-    pub pool_state: Box<Account<'info, PoolState>>, // (New) PoolState account
+    pub pool_state: Box<Account<'info, PoolState>>, // PoolState account
 
     #[account(address = pool_state.base_mint)]
     pub base_mint: Box<InterfaceAccount<'info, Mint>> // Token mint address
@@ -20,7 +20,7 @@ use crate::{
 //   Ok on success, ErrorCode on failure
 pub fn withdraw(ctx: Context<AWithdraw>) -> Result<()> {
     let withdrawer = ctx.accounts.withdrawer.to_account_info();
-    let main_state = &ctx.accounts.main_state;
+    let main_state = &mut ctx.accounts.main_state;
     let pool_state = &mut ctx.accounts.pool_state;
 
     require!(pool_state.complete.eq(&true), MaxiFarmError::BondingCurveIncomplete); // BondingCurve must be complete
@@ -69,6 +69,7 @@ pub fn withdraw(ctx: Context<AWithdraw>) -> Result<()> {
         timestamp: Clock::get()?.unix_timestamp
     });
 
+    main_state.total_base_withdrawn += pool_state.real_base_reserves;
     pool_state.real_base_reserves = 0;
     pool_state.real_quote_reserves = 0;
     
@@ -83,6 +84,7 @@ pub struct AWithdraw<'info> {
     pub withdrawer: Signer<'info>, // Current withdrawer
 
     #[account(
+        mut,
         seeds = [MainState::PREFIX_SEED],
         bump,
         has_one = withdrawer
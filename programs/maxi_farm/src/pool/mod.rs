@@ -7,5 +7,23 @@ pub use state::*;
 pub mod event;
 pub use event::*;
 
-pub mod ed25519;
-pub use ed25519::*;
+pub mod sig_verify;
+pub use sig_verify::*;
+
+pub mod permit;
+pub use permit::*;
+
+pub mod allowlist;
+pub use allowlist::*;
+
+pub mod priv_sale;
+pub use priv_sale::*;
+
+pub mod vesting;
+pub use vesting::*;
+
+pub mod trader_stats;
+pub use trader_stats::*;
+
+pub mod token_gate;
+pub use token_gate::*;
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use crate::{
+    error::MaxiFarmError,
+    utils::create_pda_account,
+    sig_verify::{verify_ed25519_sig, verify_secp256k1_sig, SignatureScheme},
+    priv_sale::PrivSaleAccounts,
+    PoolState
+};
+
+// Private sale permit (Signed by MainState's signer)
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy)]
+pub struct SalePermit {
+    pub program_id: Pubkey,         // Program the permit is valid for
+    pub base_mint: Pubkey,          // Token mint address of the pool
+    pub trader: Pubkey,             // Wallet address allowed to trade
+    pub phase: u8,                  // Index of private sale phase the permit is valid in
+    pub is_buy: bool,               // Trade direction the permit is valid for
+    pub max_quote_amount: u64,      // Max. amount of SOL to trade
+    pub expiry: u64,                // Expiry time
+    pub nonce: u64                  // Nonce (single use)
+}
+
+// Signed private sale permit
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone)]
+pub struct SignedSalePermit {
+    pub permit: SalePermit,         // Permit
+    pub sig: Vec<u8>                // Signature over serialized permit (ed25519: 64 bytes, secp256k1: 65 bytes with recovery id)
+}
+
+// Used permit nonce
+#[account]
+pub struct PermitNonce {
+    pub trader: Pubkey,             // Wallet address that used the permit
+    pub nonce: u64,                 // Used nonce
+    pub used_time: u64              // Used time
+}
+
+impl PermitNonce {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();    // Size of PermitNonce
+    pub const PREFIX_SEED: &'static [u8] = b"permit";           // Seed of PermitNonce
+}
+
+// This function verifies signed private sale permit and consumes its nonce
+// Params
+//   pool_state - PoolState being traded
+//   accounts - Private sale accounts
+//   signed_permit - Signed private sale permit
+//   scheme - Signature scheme of the permit
+//   signer - Public key (ed25519) or Ethereum address (secp256k1) of permit signer
+//   phase_index - Index of active private sale phase
+//   is_buy - Flag indicating whether the trader buys or sells
+//   cur_timestamp - Current time
+// Return
+//   Verified permit on success, ErrorCode on failure
+#[allow(clippy::too_many_arguments)]
+pub fn verify_sale_permit(
+    pool_state: &PoolState,
+    accounts: &PrivSaleAccounts,
+    signed_permit: &SignedSalePermit,
+    scheme: SignatureScheme,
+    signer: &[u8],
+    phase_index: u8,
+    is_buy: bool,
+    cur_timestamp: u64
+) -> Result<SalePermit> {
+    let trader = &accounts.trader;
+    let permit = signed_permit.permit;
+    require!(
+        permit.program_id.eq(&crate::ID) && permit.base_mint.eq(&pool_state.base_mint) && permit.is_buy == is_buy,
+        MaxiFarmError::InvalidPermit
+    );
+    require!(permit.trader.eq(trader.key), MaxiFarmError::PermitWrongTrader);
+    require!(permit.phase.eq(&phase_index), MaxiFarmError::InvalidPermit);
+    require!(cur_timestamp < permit.expiry, MaxiFarmError::PermitExpired);
+
+    // Check that the permit was signed in a signature verification instruction of the transaction
+    let msg = permit.try_to_vec()?;
+    match scheme {
+        SignatureScheme::Ed25519 => verify_ed25519_sig(accounts.ix_sysvar, signer, &msg, &signed_permit.sig)?,
+        SignatureScheme::Secp256k1 => verify_secp256k1_sig(accounts.ix_sysvar, signer, &msg, &signed_permit.sig)?
+    }
+    msg!("Signature is valid!");
+
+    // Record nonce, so that the permit can't be replayed
+    let permit_nonce = accounts.permit_nonce.as_ref().ok_or(MaxiFarmError::MissingPermitNonce)?;
+    let nonce_bytes = permit.nonce.to_le_bytes();
+    let (permit_nonce_key, bump) = Pubkey::find_program_address(
+        &[PermitNonce::PREFIX_SEED, trader.key.as_ref(), &nonce_bytes],
+        &crate::ID
+    );
+    require!(permit_nonce.key().eq(&permit_nonce_key), MaxiFarmError::MissingPermitNonce);
+    require!(!permit_nonce.owner.eq(&crate::ID), MaxiFarmError::PermitAlreadyUsed);
+
+    create_pda_account(
+        trader.clone(),
+        permit_nonce.to_account_info(),
+        accounts.system_program.clone(),
+        8 + PermitNonce::MAX_SIZE,
+        &[PermitNonce::PREFIX_SEED, trader.key.as_ref(), &nonce_bytes, &[bump]]
+    )?;
+    PermitNonce {
+        trader: trader.key(),
+        nonce: permit.nonce,
+        used_time: cur_timestamp
+    }.try_serialize(&mut &mut permit_nonce.try_borrow_mut_data()?[..])?;
+
+    Ok(permit)
+}
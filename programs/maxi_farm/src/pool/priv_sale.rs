@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+use crate::{
+    error::MaxiFarmError,
+    utils::create_pda_account,
+    permit::{verify_sale_permit, SignedSalePermit},
+    sig_verify::SignatureScheme,
+    allowlist::{verify_allowlist_proof, AllowlistProof},
+    token_gate::TokenGateBenefits,
+    MainState, PoolState, PrivSalePhase
+};
+
+// Private sale authorization - passed to buy/sell during private sale period
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone)]
+pub enum PrivSaleAuth {
+    Permit(SignedSalePermit),       // Permit for the active phase, signed by phase's signer (or MainState's signer)
+    Secp256k1Permit(SignedSalePermit), // Permit for the active phase, signed by MainState's secp256k1_signer
+    Allowlist(AllowlistProof),      // Merkle proof against phase's allowlist root (pool's allowlist root without phases)
+    TokenGate                       // Holding of a token whose gate grants private sale allocation
+}
+
+// Checked private sale authorization
+pub struct PrivSaleGrant {
+    pub phase_index: u8,            // Index of active phase
+    pub phase: PrivSalePhase,       // Active phase
+    pub max_quote_amount: u64,      // Max. amount of SOL per trade
+    pub wallet_limit: u64,          // Max. amount of SOL a wallet can buy with during the phase
+    pub allocation_holder: Pubkey   // Key used allocation is tracked by (trader, or held NFT for collection gates)
+}
+
+// Accounts used for private sale checks
+pub struct PrivSaleAccounts<'a, 'info> {
+    pub trader: AccountInfo<'info>,                                     // Trader (pays rent of created accounts)
+    pub permit_nonce: &'a Option<UncheckedAccount<'info>>,              // PermitNonce account
+    pub priv_sale_allocation: &'a Option<UncheckedAccount<'info>>,      // PrivSaleAllocation account
+    pub ix_sysvar: &'a AccountInfo<'info>,                              // Instructions sysvar
+    pub system_program: AccountInfo<'info>,                             // System program
+    pub token_gate: &'a TokenGateBenefits                               // Benefits granted by token gates
+}
+
+// Used private sale allocation of a wallet in a phase
+#[account]
+pub struct PrivSaleAllocation {
+    pub pool: Pubkey,               // PoolState address
+    pub trader: Pubkey,             // Wallet address (held NFT mint for collection gate access)
+    pub phase: u8,                  // Phase index
+    pub used_amount: u64            // Amount of SOL already bought with
+}
+
+impl PrivSaleAllocation {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();    // Size of PrivSaleAllocation
+    pub const PREFIX_SEED: &'static [u8] = b"allocation";       // Seed of PrivSaleAllocation
+}
+
+// This function checks private sale authorization if private sale period has not elapsed
+// Params
+//   main_state - MainState holding signers
+//   pool_state - PoolState being traded
+//   accounts - Private sale accounts
+//   auth - Private sale authorization
+//   is_buy - Flag indicating whether the trader buys or sells
+//   cur_timestamp - Current time
+// Return
+//   Checked authorization during private sale period, None after it
+//   ErrorCode on failure
+pub fn check_priv_sale_access(
+    main_state: &MainState,
+    pool_state: &PoolState,
+    accounts: &PrivSaleAccounts,
+    auth: &Option<PrivSaleAuth>,
+    is_buy: bool,
+    cur_timestamp: u64
+) -> Result<Option<PrivSaleGrant>> {
+    let (phase_index, phase) = match pool_state.priv_sale_phase(cur_timestamp) {
+        Some(active) => active,
+        None => return Ok(None) // If elapsed priv. sale period
+    };
+    let wallet_cap = if phase.wallet_cap > 0 { phase.wallet_cap } else { u64::MAX };
+
+    let mut allocation_holder = accounts.trader.key();
+    let (max_quote_amount, wallet_limit) = match auth.as_ref().ok_or(MaxiFarmError::MissingSignature)? {
+        PrivSaleAuth::Permit(signed_permit) => {
+            let signer = if phase.signer != Pubkey::default() { phase.signer } else { main_state.signer };
+            let permit = verify_sale_permit(pool_state, accounts, signed_permit, SignatureScheme::Ed25519, signer.as_ref(), phase_index, is_buy, cur_timestamp)?;
+            (permit.max_quote_amount, wallet_cap)
+        },
+        PrivSaleAuth::Secp256k1Permit(signed_permit) => {
+            require!(main_state.secp256k1_signer != [0; 20], MaxiFarmError::Secp256k1SignerNotSet);
+            let permit = verify_sale_permit(pool_state, accounts, signed_permit, SignatureScheme::Secp256k1, &main_state.secp256k1_signer, phase_index, is_buy, cur_timestamp)?;
+            (permit.max_quote_amount, wallet_cap)
+        },
+        PrivSaleAuth::Allowlist(proof) => {
+            // Once phases are configured, each phase only accepts its own allowlist
+            let root = if pool_state.num_priv_sale_phases > 0 { &phase.allowlist_root } else { &pool_state.allowlist_root };
+            verify_allowlist_proof(root, accounts.trader.key, proof)?;
+            (u64::MAX, proof.allocation.min(wallet_cap))
+        },
+        PrivSaleAuth::TokenGate => {
+            // Holders are always limited, so holdings passed between wallets can't buy without limit
+            require!(accounts.token_gate.priv_sale_cap > 0, MaxiFarmError::TokenGateNotSatisfied);
+            allocation_holder = accounts.token_gate.priv_sale_holder;
+            (u64::MAX, accounts.token_gate.priv_sale_cap.min(wallet_cap))
+        }
+    };
+
+    Ok(Some(PrivSaleGrant {
+        phase_index,
+        phase,
+        max_quote_amount,
+        wallet_limit,
+        allocation_holder
+    }))
+}
+
+// This function checks traded amount of SOL is within the private sale authorization
+// Params
+//   grant - Checked authorization (None after private sale period)
+//   pool - PoolState address
+//   accounts - Private sale accounts
+//   quote_amount - Traded amount of SOL
+//   is_buy - Flag indicating whether the trader buys or sells
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn check_priv_sale_amount(
+    grant: &Option<PrivSaleGrant>,
+    pool: &Pubkey,
+    accounts: &PrivSaleAccounts,
+    quote_amount: u64,
+    is_buy: bool
+) -> Result<()> {
+    let grant = match grant {
+        Some(grant) => grant,
+        None => return Ok(())
+    };
+    require!(quote_amount.le(&grant.max_quote_amount), MaxiFarmError::PermitExceeded);
+
+    // Allocation limits buying only
+    if is_buy && grant.wallet_limit != u64::MAX {
+        use_priv_sale_allocation(
+            pool,
+            grant.phase_index,
+            &grant.allocation_holder,
+            &accounts.trader,
+            accounts.priv_sale_allocation,
+            &accounts.system_program,
+            grant.wallet_limit,
+            quote_amount
+        )?;
+    }
+    Ok(())
+}
+
+// This function adds bought amount to used allocation of the wallet in the phase
+// Params
+//   pool - PoolState address
+//   phase - Phase index
+//   holder - Key the allocation is tracked by (trader, or held NFT for collection gates)
+//   trader - Trader (pays rent of created account)
+//   allocation_account - PrivSaleAllocation account
+//   system_program - System program
+//   wallet_limit - Allocation of the wallet in the phase
+//   quote_amount - Amount of SOL to buy with
+// Return
+//   Ok on success, ErrorCode on failure
+#[allow(clippy::too_many_arguments)]
+pub fn use_priv_sale_allocation<'info>(
+    pool: &Pubkey,
+    phase: u8,
+    holder: &Pubkey,
+    trader: &AccountInfo<'info>,
+    allocation_account: &Option<UncheckedAccount<'info>>,
+    system_program: &AccountInfo<'info>,
+    wallet_limit: u64,
+    quote_amount: u64
+) -> Result<()> {
+    let allocation_account = allocation_account.as_ref().ok_or(MaxiFarmError::MissingAllocationAccount)?;
+    let (allocation_key, bump) = Pubkey::find_program_address(
+        &[PrivSaleAllocation::PREFIX_SEED, pool.as_ref(), holder.as_ref(), &[phase]],
+        &crate::ID
+    );
+    require!(allocation_account.key().eq(&allocation_key), MaxiFarmError::MissingAllocationAccount);
+
+    let mut allocation = if allocation_account.owner.eq(&crate::ID) {
+        PrivSaleAllocation::try_deserialize(&mut &allocation_account.try_borrow_data()?[..])?
+    } else {
+        create_pda_account(
+            trader.clone(),
+            allocation_account.to_account_info(),
+            system_program.clone(),
+            8 + PrivSaleAllocation::MAX_SIZE,
+            &[PrivSaleAllocation::PREFIX_SEED, pool.as_ref(), holder.as_ref(), &[phase], &[bump]]
+        )?;
+        PrivSaleAllocation {
+            pool: *pool,
+            trader: *holder,
+            phase,
+            used_amount: 0
+        }
+    };
+
+    allocation.used_amount += quote_amount;
+    require!(allocation.used_amount.le(&wallet_limit), MaxiFarmError::ExceededAllocation);
+    allocation.try_serialize(&mut &mut allocation_account.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
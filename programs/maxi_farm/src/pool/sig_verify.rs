@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use solana_program::{
+    instruction::Instruction,
+    ed25519_program::ID as ED25519_ID,
+    secp256k1_program::ID as SECP256K1_ID,
+    sysvar::instructions::load_instruction_at_checked
+};
+use crate::{
+    constants::*,
+    error::*
+};
+
+// Signature scheme of private sale permits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureScheme {
+    Ed25519,                        // Signed by MainState's signer
+    Secp256k1                       // Signed by MainState's secp256k1_signer
+}
+
+/// Search the instructions sysvar for an Ed25519Program instruction verifying the signature
+pub fn verify_ed25519_sig(ix_sysvar: &AccountInfo, pubkey: &[u8], msg: &[u8], sig: &[u8]) -> Result<()> {
+    require!(pubkey.len() == PUBKEY_LEN, MaxiFarmError::InvalidPubkeyLen);
+    require!(sig.len() == SIG_LEN, MaxiFarmError::InvalidSigLen);
+
+    // Instructions sysvar starts with number of instructions (u16)
+    for index in 0..num_instructions(ix_sysvar)? {
+        let ix = load_instruction_at_checked(index, ix_sysvar)?;
+        if ix.program_id == ED25519_ID && check_ed25519_data(&ix, ix_sysvar, pubkey, msg, sig)? {
+            return Ok(());
+        }
+    }
+
+    err!(MaxiFarmError::SigVerificationFailed)
+}
+
+/// Check whether any signature of Ed25519Program instruction matches the arguments
+fn check_ed25519_data(ix: &Instruction, ix_sysvar: &AccountInfo, pubkey: &[u8], msg: &[u8], sig: &[u8]) -> Result<bool> {
+    // According to this layout used by the Ed25519Program
+    // https://github.com/solana-labs/solana/blob/master/sdk/src/ed25519_instruction.rs
+    //   num_signatures (u8), padding (u8), then per signature:
+    //   signature_offset, signature_instruction_index, public_key_offset, public_key_instruction_index,
+    //   message_data_offset, message_data_size, message_instruction_index (u16 each)
+    let data = &ix.data;
+    require!(data.len() >= SIG_OFFSETS_START, MaxiFarmError::TooShortDataLen);
+    let num_signatures = data[0] as usize;
+    require!(
+        data.len() >= SIG_OFFSETS_START + num_signatures * SIG_OFFSETS_LEN,
+        MaxiFarmError::InvalidMessageFormat
+    );
+
+    for i in 0..num_signatures {
+        let offsets = &data[SIG_OFFSETS_START + i * SIG_OFFSETS_LEN..SIG_OFFSETS_START + (i + 1) * SIG_OFFSETS_LEN];
+        let read_u16 = |pos: usize| u16::from_le_bytes([offsets[pos], offsets[pos + 1]]);
+
+        if read_u16(10) as usize != msg.len() {
+            continue;
+        }
+        let data_sig = ed25519_ix_slice(ix, ix_sysvar, read_u16(2), read_u16(0), SIG_LEN)?;
+        let data_pubkey = ed25519_ix_slice(ix, ix_sysvar, read_u16(6), read_u16(4), PUBKEY_LEN)?;
+        let data_msg = ed25519_ix_slice(ix, ix_sysvar, read_u16(12), read_u16(8), msg.len())?;
+
+        if data_sig.as_deref() == Some(sig) && data_pubkey.as_deref() == Some(pubkey) && data_msg.as_deref() == Some(msg) {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Read bytes referenced by Ed25519Program offsets (u16::MAX index refers to the instruction itself)
+fn ed25519_ix_slice(ix: &Instruction, ix_sysvar: &AccountInfo, ix_index: u16, offset: u16, len: usize) -> Result<Option<Vec<u8>>> {
+    let start = offset as usize;
+    if ix_index == u16::MAX {
+        return Ok(ix.data.get(start..start + len).map(|s| s.to_vec()));
+    }
+    let referenced_ix = load_instruction_at_checked(ix_index as usize, ix_sysvar)?;
+    Ok(referenced_ix.data.get(start..start + len).map(|s| s.to_vec()))
+}
+
+/// Search the instructions sysvar for a Secp256k1Program instruction verifying the signature
+///   Secp256k1Program recovers signer of keccak256(msg), so msg is signed without Ethereum prefix
+pub fn verify_secp256k1_sig(ix_sysvar: &AccountInfo, eth_address: &[u8], msg: &[u8], sig: &[u8]) -> Result<()> {
+    require!(eth_address.len() == ETH_ADDRESS_LEN, MaxiFarmError::InvalidPubkeyLen);
+    require!(sig.len() == SECP256K1_SIG_LEN, MaxiFarmError::InvalidSigLen);
+
+    for index in 0..num_instructions(ix_sysvar)? {
+        let ix = load_instruction_at_checked(index, ix_sysvar)?;
+        if ix.program_id == SECP256K1_ID && check_secp256k1_data(&ix, ix_sysvar, eth_address, msg, sig)? {
+            return Ok(());
+        }
+    }
+
+    err!(MaxiFarmError::SigVerificationFailed)
+}
+
+/// Check whether any signature of Secp256k1Program instruction matches the arguments
+fn check_secp256k1_data(ix: &Instruction, ix_sysvar: &AccountInfo, eth_address: &[u8], msg: &[u8], sig: &[u8]) -> Result<bool> {
+    // According to this layout used by the Secp256k1Program
+    // https://github.com/solana-labs/solana/blob/master/sdk/src/secp256k1_instruction.rs
+    //   num_signatures (u8), then per signature:
+    //   signature_offset (u16), signature_instruction_index (u8), eth_address_offset (u16), eth_address_instruction_index (u8),
+    //   message_data_offset (u16), message_data_size (u16), message_instruction_index (u8)
+    let data = &ix.data;
+    require!(data.len() >= SECP256K1_OFFSETS_START, MaxiFarmError::TooShortDataLen);
+    let num_signatures = data[0] as usize;
+    require!(
+        data.len() >= SECP256K1_OFFSETS_START + num_signatures * SECP256K1_OFFSETS_LEN,
+        MaxiFarmError::InvalidMessageFormat
+    );
+
+    for i in 0..num_signatures {
+        let offsets = &data[SECP256K1_OFFSETS_START + i * SECP256K1_OFFSETS_LEN..SECP256K1_OFFSETS_START + (i + 1) * SECP256K1_OFFSETS_LEN];
+        let read_u16 = |pos: usize| u16::from_le_bytes([offsets[pos], offsets[pos + 1]]);
+
+        if read_u16(8) as usize != msg.len() {
+            continue;
+        }
+        let data_sig = secp256k1_ix_slice(ix_sysvar, offsets[2], read_u16(0), SECP256K1_SIG_LEN)?;
+        let data_address = secp256k1_ix_slice(ix_sysvar, offsets[5], read_u16(3), ETH_ADDRESS_LEN)?;
+        let data_msg = secp256k1_ix_slice(ix_sysvar, offsets[10], read_u16(6), msg.len())?;
+
+        if data_sig.as_deref() == Some(sig) && data_address.as_deref() == Some(eth_address) && data_msg.as_deref() == Some(msg) {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Read bytes referenced by Secp256k1Program offsets (index is always absolute)
+fn secp256k1_ix_slice(ix_sysvar: &AccountInfo, ix_index: u8, offset: u16, len: usize) -> Result<Option<Vec<u8>>> {
+    let start = offset as usize;
+    let referenced_ix = load_instruction_at_checked(ix_index as usize, ix_sysvar)?;
+    Ok(referenced_ix.data.get(start..start + len).map(|s| s.to_vec()))
+}
+
+/// Read number of instructions from the instructions sysvar
+fn num_instructions(ix_sysvar: &AccountInfo) -> Result<usize> {
+    // Instructions sysvar starts with number of instructions (u16)
+    let data = ix_sysvar.try_borrow_data()?;
+    require!(data.len() >= 2, MaxiFarmError::TooShortDataLen);
+    Ok(u16::from_le_bytes([data[0], data[1]]) as usize)
+}
@@ -1,10 +1,94 @@
 use anchor_lang::prelude::*;
+use crate::{
+    constants::{MAX_PRIV_SALE_PHASES, MAX_PRICE_PREMIUM, MAX_TAX, MAX_TAX_DECAY_DURATION},
+    error::MaxiFarmError,
+    TokenGate
+};
+
+// Private sale phase
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrivSalePhase {
+    pub start_offset: u64,          // Phase start time, offset from trading start
+    pub signer: Pubkey,             // Permit signer (zero: MainState's signers)
+    pub allowlist_root: [u8; 32],   // Merkle root of allowlist (zero: no allowlist access in the phase)
+    pub wallet_cap: u64,            // Max. amount of SOL a wallet can buy with during the phase (0: no cap)
+    pub price_premium: u64          // Extra SOL paid into the bonding curve on buying (100 = 1%)
+}
+
+// Decay curve of launch tax
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TaxCurve {
+    #[default]
+    Linear,                         // Decays at a constant speed
+    Quadratic                       // Decays fast at first, then slows down
+}
+
+// Launch tax schedule, decaying from start rate to end rate
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaxSchedule {
+    pub start_rate: u64,            // Tax at trading start
+    pub end_rate: u64,              // Tax after decay duration
+    pub decay_duration: u64,        // Duration of decay, from trading start
+    pub curve: TaxCurve             // Decay curve
+}
+
+impl TaxSchedule {
+    // This function checks whether the schedule is valid
+    // Params
+    //   self - TaxSchedule struct itself
+    //   max_end_rate - Maximum tax allowed after decay
+    // Return
+    //   Ok on success, ErrorCode on failure
+    pub fn validate(&self, max_end_rate: u64) -> Result<()> {
+        require!(
+            self.start_rate.le(&MAX_TAX)
+                && self.end_rate.le(&self.start_rate)
+                && self.end_rate.le(&max_end_rate)
+                && self.decay_duration.le(&MAX_TAX_DECAY_DURATION),
+            MaxiFarmError::InvalidTaxSchedule
+        );
+        Ok(())
+    }
+
+    // This function checks whether the schedule charges no more than the other at any time
+    // Params
+    //   self - TaxSchedule struct itself
+    //   other - Schedule to compare with
+    // Return
+    //   true if every rate is lowered (or kept) and decay is not slowed down
+    pub fn is_lower_than(&self, other: &TaxSchedule) -> bool {
+        self.curve == other.curve
+            && self.start_rate <= other.start_rate
+            && self.end_rate <= other.end_rate
+            && self.decay_duration <= other.decay_duration
+    }
+
+    // This function returns tax in effect
+    // Params
+    //   self - TaxSchedule struct itself
+    //   elapsed - Time elapsed since trading start
+    // Return
+    //   Tax rate
+    pub fn rate_at(&self, elapsed: u64) -> u64 {
+        if elapsed >= self.decay_duration {
+            return self.end_rate;
+        }
+        let range = self.start_rate.saturating_sub(self.end_rate) as u128;
+        let remaining = (self.decay_duration - elapsed) as u128;
+        let duration = self.decay_duration as u128;
+        let decaying = match self.curve {
+            TaxCurve::Linear => range * remaining / duration,
+            TaxCurve::Quadratic => range * remaining * remaining / (duration * duration)
+        };
+        self.end_rate + decaying as u64
+    }
+}
 
 // BondingCurve struct
 #[account]
 pub struct PoolState {
     pub owner: Pubkey,              // BondingCurve creator
-    pub tax: u64,                   // Transfer tax
+    pub buy_tax: u64,               // Tax on buys (SOL) paid to pool owner (transfer tax in the initial layout, reset on migration)
     pub max_fee_tokens: u64,        // Max. fee tokens
     pub base_mint: Pubkey,          // Token mint address
     pub virt_base_reserves: u64,    // Amount of virtual tokens in the bonding curve
@@ -18,13 +102,138 @@ pub struct PoolState {
     pub real_quote_threshold: u64,  // Real SOL threshold
     pub created_time: u64,          // Created time
     pub priv_sale_period: u64,      // Private sale period
-    pub complete: bool              // Flag indicating whether the bonding curve is complete or not
+    pub complete: bool,             // Flag indicating whether the bonding curve is complete or not
+    pub allowlist_root: [u8; 32],   // Merkle root of private sale allowlist (zero if not set)
+    pub num_priv_sale_phases: u8,   // Number of private sale phases (0: single phase for the whole private sale period)
+    pub priv_sale_phases: [PrivSalePhase; MAX_PRIV_SALE_PHASES], // Private sale phases, ordered by start offset
+    pub start_time: u64,            // Trading start time (0: created time)
+    pub trade_count: u64,           // Number of trades
+    pub sell_tax: u64,              // Tax on sells (SOL) paid to pool owner
+    pub tax_schedule: TaxSchedule,  // Launch tax schedule (applied if higher than buy/sell tax)
+    pub last_tax_update: u64,       // Time of the last tax update (0: never updated)
+    pub renounced: bool,            // Flag indicating whether tax and pool owner powers are renounced
+    pub pending_owner: Pubkey,      // Proposed new owner (zero: no proposal)
+    pub trading_fee: Option<u64>,   // Trading fee override (None: MainState's trading fee)
+    pub token_gate: TokenGate,      // Pool benefits for token holders
+    pub max_tax_increase: u64,      // Max. raise of buy/sell tax per update (fixed at creation, 0 for migrated pools: lowering only)
+    pub tax_update_cooldown: u64    // Min. time between tax updates (fixed at creation)
 }
 
 impl PoolState {
     pub const MAX_SIZE: usize = std::mem::size_of::<Self>();    // Size of PoolState
+    pub const INITIAL_SIZE: usize = 144;                        // Size of PoolState in the initial layout
     pub const PREFIX_SEED: &'static [u8] = b"pool";             // Seed of PoolState
 
+    // This function returns the time trading starts
+    // Params
+    //   self - PoolState struct itself
+    // Return
+    //   Scheduled start time, or created time if not scheduled
+    pub fn trading_start_time(&self) -> u64 {
+        if self.start_time == 0 {
+            return self.created_time;
+        }
+        self.start_time
+    }
+
+    // This function returns the time public trading opens
+    // Params
+    //   self - PoolState struct itself
+    // Return
+    //   End of private sale period
+    pub fn public_sale_time(&self) -> u64 {
+        self.trading_start_time() + self.priv_sale_period
+    }
+
+    // This function returns whether private sale config (allowlist root and phases) is locked
+    // Params
+    //   self - PoolState struct itself
+    //   timestamp - Time to check
+    // Return
+    //   true once trading started, else false
+    pub fn priv_sale_locked(&self, timestamp: u64) -> bool {
+        timestamp >= self.trading_start_time()
+    }
+
+    // This function sets private sale phases
+    // Params
+    //   self - PoolState struct itself
+    //   phases - Private sale phases, ordered by start offset (empty for a single phase)
+    // Return
+    //   Ok on success, ErrorCode on failure
+    pub fn set_priv_sale_phases(&mut self, phases: &[PrivSalePhase]) -> Result<()> {
+        // First phase starts at trading start, and the rest start in order within private sale period
+        require!(phases.len() <= MAX_PRIV_SALE_PHASES, MaxiFarmError::InvalidPrivSalePhases);
+        for (i, phase) in phases.iter().enumerate() {
+            let valid_start = if i == 0 {
+                phase.start_offset == 0
+            } else {
+                phase.start_offset > phases[i - 1].start_offset && phase.start_offset < self.priv_sale_period
+            };
+            require!(valid_start, MaxiFarmError::InvalidPrivSalePhases);
+            require!(phase.price_premium.le(&MAX_PRICE_PREMIUM), MaxiFarmError::InvalidPrivSalePhases);
+        }
+
+        self.num_priv_sale_phases = phases.len() as u8;
+        self.priv_sale_phases = [PrivSalePhase::default(); MAX_PRIV_SALE_PHASES];
+        self.priv_sale_phases[..phases.len()].copy_from_slice(phases);
+        Ok(())
+    }
+
+    // This function returns private sale phase active at the time
+    // Params
+    //   self - PoolState struct itself
+    //   timestamp - Time to check
+    // Return
+    //   Index and phase, None after private sale period
+    pub fn priv_sale_phase(&self, timestamp: u64) -> Option<(u8, PrivSalePhase)> {
+        if timestamp >= self.public_sale_time() {
+            return None;
+        }
+        if self.num_priv_sale_phases == 0 {
+            return Some((0, PrivSalePhase::default()));
+        }
+
+        // First phase starts at trading start, so there's always an active phase
+        let elapsed = timestamp.saturating_sub(self.trading_start_time());
+        let index = (0..self.num_priv_sale_phases as usize)
+            .rev()
+            .find(|&i| self.priv_sale_phases[i].start_offset <= elapsed)
+            .unwrap_or(0);
+        Some((index as u8, self.priv_sale_phases[index]))
+    }
+
+    // This function returns tax applied on buying tokens
+    // Params
+    //   self - PoolState struct itself
+    //   timestamp - Time of the trade
+    // Return
+    //   Buy tax, or launch tax in effect if higher
+    pub fn buy_tax_at(&self, timestamp: u64) -> u64 {
+        self.buy_tax.max(self.tax_schedule.rate_at(timestamp.saturating_sub(self.trading_start_time())))
+    }
+
+    // This function returns tax applied on selling tokens
+    // Params
+    //   self - PoolState struct itself
+    //   timestamp - Time of the trade
+    // Return
+    //   Sell tax, or launch tax in effect if higher
+    pub fn sell_tax_at(&self, timestamp: u64) -> u64 {
+        self.sell_tax.max(self.tax_schedule.rate_at(timestamp.saturating_sub(self.trading_start_time())))
+    }
+
+    // This function returns whether launch tax is still decaying
+    // Params
+    //   self - PoolState struct itself
+    //   timestamp - Time to check
+    // Return
+    //   true if a schedule is set and its decay has not ended, else false
+    pub fn tax_schedule_active(&self, timestamp: u64) -> bool {
+        self.tax_schedule.start_rate > 0
+            && timestamp.saturating_sub(self.trading_start_time()) < self.tax_schedule.decay_duration
+    }
+
     // This function calculates receivable amount on buying tokens
     // Params
     //   self - PoolState struct itself
@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use std::str::FromStr;
+use crate::{
+    constants::{BPS, MAX_HOLDER_DISCOUNT, METADATA_PROGRAM_ID_STR},
+    error::MaxiFarmError,
+    MainState, PoolState
+};
+
+// Kind of token a gate checks
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TokenGateKind {
+    #[default]
+    Mint,                           // Holding of an SPL/Token-2022 mint
+    Collection                      // Holding of an NFT in a verified Metaplex collection
+}
+
+// Benefits granted to holders of a token
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenGate {
+    pub kind: TokenGateKind,        // Kind of gate
+    pub key: Pubkey,                // Token mint, or collection mint (zero: disabled)
+    pub min_balance: u64,           // Min. balance of the holding account
+    pub fee_discount: u64,          // Discount on trading fee (100 = 1%)
+    pub priv_sale_cap: u64          // Max. amount of SOL a holding can buy with per private sale phase (0: no private sale access)
+}
+
+impl TokenGate {
+    // This function checks whether the gate is valid
+    // Params
+    //   self - TokenGate struct itself
+    // Return
+    //   Ok on success, ErrorCode on failure
+    pub fn validate(&self) -> Result<()> {
+        require!(self.fee_discount.le(&MAX_HOLDER_DISCOUNT), MaxiFarmError::InvalidTokenGate);
+        Ok(())
+    }
+}
+
+// Benefits a trader is granted by token gates
+#[derive(Default)]
+pub struct TokenGateBenefits {
+    pub fee_discount: u64,          // Discount on trading fee (highest of satisfied gates)
+    pub priv_sale_cap: u64,         // Private sale allocation per phase (highest of satisfied gates, 0: no access)
+    pub priv_sale_holder: Pubkey    // Key the allocation is tracked by (held NFT for collection gates, else trader)
+}
+
+impl TokenGateBenefits {
+    // This function applies holder discount to trading fee
+    // Params
+    //   self - TokenGateBenefits struct itself
+    //   base_fee - Trading fee in effect for the trader
+    // Return
+    //   Discounted trading fee
+    pub fn discounted_fee(&self, base_fee: u64) -> u64 {
+        base_fee - base_fee * self.fee_discount / (100 * BPS)
+    }
+}
+
+// Collection of Metaplex metadata
+#[derive(AnchorDeserialize)]
+struct MetadataCollection {
+    verified: bool,
+    key: Pubkey
+}
+
+// Creator of Metaplex metadata
+#[derive(AnchorDeserialize)]
+struct MetadataCreator {
+    _address: Pubkey,
+    _verified: bool,
+    _share: u8
+}
+
+// Leading fields of Metaplex metadata, up to collection
+#[derive(AnchorDeserialize)]
+struct MetadataPrefix {
+    _key: u8,
+    _update_authority: Pubkey,
+    mint: Pubkey,
+    _name: String,
+    _symbol: String,
+    _uri: String,
+    _seller_fee_basis_points: u16,
+    _creators: Option<Vec<MetadataCreator>>,
+    _primary_sale_happened: bool,
+    _is_mutable: bool,
+    _edition_nonce: Option<u8>,
+    _token_standard: Option<u8>,
+    collection: Option<MetadataCollection>
+}
+
+// This function checks whether a holding satisfies the gate
+// Params
+//   gate - Token gate
+//   holding - Trader's holding account
+//   metadata - Metaplex metadata account of the held mint (collection gates only)
+// Return
+//   true if satisfied (invalid metadata account doesn't satisfy the gate)
+fn is_gate_satisfied(gate: &TokenGate, holding: &TokenAccount, metadata: &Option<UncheckedAccount>) -> Result<bool> {
+    if gate.key == Pubkey::default() || holding.amount < gate.min_balance.max(1) {
+        return Ok(false);
+    }
+    match gate.kind {
+        TokenGateKind::Mint => Ok(holding.mint == gate.key),
+        TokenGateKind::Collection => {
+            let metadata = match metadata {
+                Some(metadata) => metadata,
+                None => return Ok(false)
+            };
+            let metadata_program = Pubkey::from_str(METADATA_PROGRAM_ID_STR).unwrap();
+            let (metadata_key, _) = Pubkey::find_program_address(
+                &[b"metadata", metadata_program.as_ref(), holding.mint.as_ref()],
+                &metadata_program
+            );
+            if !metadata.key().eq(&metadata_key) || !metadata.owner.eq(&metadata_program) {
+                return Ok(false);
+            }
+
+            let prefix = match MetadataPrefix::deserialize(&mut &metadata.try_borrow_data()?[..]) {
+                Ok(prefix) => prefix,
+                Err(_) => return Ok(false)
+            };
+            Ok(prefix.mint == holding.mint
+                && prefix.collection.is_some_and(|collection| collection.verified && collection.key == gate.key))
+        }
+    }
+}
+
+// This function checks MainState's and pool's token gates
+//   Balance is checked at the time of the trade only, so a holding can be passed between wallets to share the benefits
+//   Private sale allocation of collection gates is tracked per held NFT, so passing it on doesn't grant more
+// Params
+//   main_state - MainState holding protocol gate
+//   pool_state - PoolState holding pool gate
+//   trader - Trader's wallet address
+//   holding - Trader's holding account (None: no benefits)
+//   metadata - Metaplex metadata account of the held mint (collection gates only)
+// Return
+//   Granted benefits, ErrorCode if holding account is invalid
+pub fn check_token_gates(
+    main_state: &MainState,
+    pool_state: &PoolState,
+    trader: &Pubkey,
+    holding: &Option<Box<InterfaceAccount<TokenAccount>>>,
+    metadata: &Option<UncheckedAccount>
+) -> Result<TokenGateBenefits> {
+    let holding = match holding {
+        Some(holding) => holding,
+        None => return Ok(TokenGateBenefits::default())
+    };
+    require!(holding.owner.eq(trader), MaxiFarmError::InvalidTokenGateAccount);
+
+    let mut benefits = TokenGateBenefits::default();
+    for gate in [&main_state.token_gate, &pool_state.token_gate] {
+        if is_gate_satisfied(gate, holding, metadata)? {
+            benefits.fee_discount = benefits.fee_discount.max(gate.fee_discount);
+            if gate.priv_sale_cap > benefits.priv_sale_cap {
+                benefits.priv_sale_cap = gate.priv_sale_cap;
+                benefits.priv_sale_holder = if gate.kind == TokenGateKind::Collection { holding.mint } else { *trader };
+            }
+        }
+    }
+    Ok(benefits)
+}
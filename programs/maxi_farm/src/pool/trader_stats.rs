@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::{SECONDS_PER_DAY, VOLUME_WINDOW_DAYS},
+    error::MaxiFarmError,
+    utils::create_pda_account
+};
+
+// Trading volume of a trader over the rolling window
+#[account]
+pub struct TraderStats {
+    pub trader: Pubkey,             // Wallet address
+    pub last_day: u64,              // Day of the last recorded trade (unix time / 1d)
+    pub daily_volume: [u64; VOLUME_WINDOW_DAYS] // SOL volume per day, indexed by day % VOLUME_WINDOW_DAYS
+}
+
+impl TraderStats {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();    // Size of TraderStats
+    pub const PREFIX_SEED: &'static [u8] = b"stats";            // Seed of TraderStats
+
+    // This function returns SOL volume within the rolling window
+    // Params
+    //   self - TraderStats struct itself
+    //   timestamp - Current time
+    // Return
+    //   Sum of daily volumes of the last VOLUME_WINDOW_DAYS days
+    pub fn rolling_volume(&self, timestamp: u64) -> u64 {
+        let day = timestamp / SECONDS_PER_DAY;
+        let window = VOLUME_WINDOW_DAYS as u64;
+        let first_day = (day + 1).saturating_sub(window).max((self.last_day + 1).saturating_sub(window));
+        (first_day..=self.last_day)
+            .map(|d| self.daily_volume[(d % window) as usize])
+            .sum()
+    }
+
+    // This function records SOL volume of a trade
+    // Params
+    //   self - TraderStats struct itself
+    //   timestamp - Time of the trade
+    //   volume - Traded amount of SOL
+    pub fn record(&mut self, timestamp: u64, volume: u64) {
+        let day = timestamp / SECONDS_PER_DAY;
+        let window = VOLUME_WINDOW_DAYS as u64;
+        // Clear days skipped since the last trade, which are out of the window now
+        if day > self.last_day {
+            let first_day = (self.last_day + 1).max((day + 1).saturating_sub(window));
+            for d in first_day..=day {
+                self.daily_volume[(d % window) as usize] = 0;
+            }
+            self.last_day = day;
+        }
+        self.daily_volume[(self.last_day % window) as usize] += volume;
+    }
+}
+
+// This function returns rolling volume of a trader
+// Params
+//   stats_account - TraderStats account of the trader (None: no volume discount)
+//   trader - Wallet address
+//   timestamp - Current time
+// Return
+//   Rolling volume, 0 if not created yet, None if stats account is not passed
+pub fn trader_volume(stats_account: &Option<UncheckedAccount>, trader: &Pubkey, timestamp: u64) -> Result<Option<u64>> {
+    let stats_account = match stats_account {
+        Some(stats_account) => stats_account,
+        None => return Ok(None)
+    };
+    let (stats_key, _) = Pubkey::find_program_address(&[TraderStats::PREFIX_SEED, trader.as_ref()], &crate::ID);
+    require!(stats_account.key().eq(&stats_key), MaxiFarmError::InvalidTraderStats);
+
+    if !stats_account.owner.eq(&crate::ID) {
+        return Ok(Some(0));
+    }
+    let stats = TraderStats::try_deserialize(&mut &stats_account.try_borrow_data()?[..])?;
+    Ok(Some(stats.rolling_volume(timestamp)))
+}
+
+// This function records volume of a trade in TraderStats (created on first use)
+// Params
+//   stats_account - TraderStats account of the trader (None: not recorded)
+//   trader - Trader (pays rent on creation)
+//   system_program - System program
+//   timestamp - Time of the trade
+//   volume - Traded amount of SOL
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn record_trader_volume<'info>(
+    stats_account: &Option<UncheckedAccount<'info>>,
+    trader: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    timestamp: u64,
+    volume: u64
+) -> Result<()> {
+    let stats_account = match stats_account {
+        Some(stats_account) => stats_account,
+        None => return Ok(())
+    };
+    let (stats_key, bump) = Pubkey::find_program_address(&[TraderStats::PREFIX_SEED, trader.key.as_ref()], &crate::ID);
+    require!(stats_account.key().eq(&stats_key), MaxiFarmError::InvalidTraderStats);
+
+    let mut stats = if stats_account.owner.eq(&crate::ID) {
+        TraderStats::try_deserialize(&mut &stats_account.try_borrow_data()?[..])?
+    } else {
+        create_pda_account(
+            trader.clone(),
+            stats_account.to_account_info(),
+            system_program.clone(),
+            8 + TraderStats::MAX_SIZE,
+            &[TraderStats::PREFIX_SEED, trader.key.as_ref(), &[bump]]
+        )?;
+        TraderStats {
+            trader: trader.key(),
+            last_day: 0,
+            daily_volume: [0; VOLUME_WINDOW_DAYS]
+        }
+    };
+
+    stats.record(timestamp, volume);
+    stats.try_serialize(&mut &mut stats_account.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::MAX_CREATOR_VESTING_DURATION,
+    error::MaxiFarmError,
+    utils::create_pda_account
+};
+
+// Vesting schedule of creator allocation
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy)]
+pub struct VestingSchedule {
+    pub cliff_duration: u64,        // Nothing unlocks before the cliff
+    pub vesting_duration: u64       // Allocation unlocks linearly over this duration (cliff included)
+}
+
+// Creator allocation held in vesting escrow
+//   Escrowed tokens are held by a token account owned by this PDA
+#[account]
+pub struct CreatorVesting {
+    pub beneficiary: Pubkey,        // Creator wallet address (kept on pool ownership transfer)
+    pub base_mint: Pubkey,          // Token mint address
+    pub total_amount: u64,          // Amount of tokens allocated to the creator
+    pub claimed_amount: u64,        // Amount of tokens already claimed
+    pub start_time: u64,            // Vesting start time (trading start)
+    pub cliff_duration: u64,        // Nothing unlocks before the cliff
+    pub vesting_duration: u64       // Allocation unlocks linearly over this duration
+}
+
+impl CreatorVesting {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();    // Size of CreatorVesting
+    pub const PREFIX_SEED: &'static [u8] = b"vesting";          // Seed of CreatorVesting
+
+    // This function calculates claimable amount of tokens
+    // Params
+    //   self - CreatorVesting struct itself
+    //   timestamp - Current time
+    // Return
+    //   Unlocked but not claimed amount of tokens
+    pub fn claimable_amount(&self, timestamp: u64) -> u64 {
+        let elapsed = timestamp.saturating_sub(self.start_time);
+        if elapsed < self.cliff_duration {
+            return 0;
+        }
+        let unlocked = if elapsed >= self.vesting_duration {
+            self.total_amount
+        } else {
+            (self.total_amount as u128 * elapsed as u128 / self.vesting_duration as u128) as u64
+        };
+        unlocked - self.claimed_amount
+    }
+}
+
+// This function creates vesting escrow of creator allocation
+// Params
+//   creator - Creator (pays rent)
+//   vesting_account - CreatorVesting PDA
+//   system_program - System program
+//   base_mint - Token mint address
+//   total_amount - Allocated amount of tokens
+//   start_time - Vesting start time
+//   schedule - Vesting schedule
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn init_creator_vesting<'info>(
+    creator: &AccountInfo<'info>,
+    vesting_account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    base_mint: &Pubkey,
+    total_amount: u64,
+    start_time: u64,
+    schedule: VestingSchedule
+) -> Result<()> {
+    require!(
+        schedule.vesting_duration.gt(&0)
+            && schedule.vesting_duration.le(&MAX_CREATOR_VESTING_DURATION)
+            && schedule.cliff_duration.le(&schedule.vesting_duration),
+        MaxiFarmError::InvalidVestingSchedule
+    );
+
+    let (vesting_key, bump) = Pubkey::find_program_address(&[CreatorVesting::PREFIX_SEED, base_mint.as_ref()], &crate::ID);
+    require!(vesting_account.key().eq(&vesting_key), MaxiFarmError::InvalidCreatorAllocation);
+
+    create_pda_account(
+        creator.clone(),
+        vesting_account.clone(),
+        system_program.clone(),
+        8 + CreatorVesting::MAX_SIZE,
+        &[CreatorVesting::PREFIX_SEED, base_mint.as_ref(), &[bump]]
+    )?;
+    CreatorVesting {
+        beneficiary: creator.key(),
+        base_mint: *base_mint,
+        total_amount,
+        claimed_amount: 0,
+        start_time,
+        cliff_duration: schedule.cliff_duration,
+        vesting_duration: schedule.vesting_duration
+    }.try_serialize(&mut &mut vesting_account.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
@@ -12,6 +12,35 @@ pub struct UserRegisteredEvent {
 #[event]
 pub struct RewardsClaimEvent {
     pub user: Pubkey, // User wallet address
+    pub destination: Pubkey, // Address receiving rewards
     pub rewards: u64, // Rewards amount
     pub timestamp: i64 // Registered time
-}
\ No newline at end of file
+}
+
+// Custom reward updated event
+#[event]
+pub struct CustomRewardUpdatedEvent {
+    pub authority: Pubkey, // Owner or referral manager
+    pub referrer: Pubkey, // Referrer wallet address
+    pub tier1_reward: u64, // Custom tier1 reward (0: global tier1 reward)
+    pub expiry: u64, // Expiry time (0: never expires)
+    pub timestamp: i64 // Updated time
+}
+
+// Claim config updated event
+#[event]
+pub struct ClaimConfigUpdatedEvent {
+    pub user: Pubkey, // User wallet address
+    pub delegate: Pubkey, // Address allowed to claim on behalf of user
+    pub destination: Pubkey, // Address receiving claimed rewards
+    pub timestamp: i64 // Updated time
+}
+
+// Expired rewards swept event
+#[event]
+pub struct RewardsSweptEvent {
+    pub authority: Pubkey, // Owner or referral manager
+    pub user: Pubkey, // User wallet address
+    pub rewards: u64, // Swept rewards amount
+    pub timestamp: i64 // Swept time
+}
@@ -10,33 +10,112 @@ use crate::{
     RewardsClaimEvent,
 };
 
-pub fn claim_rewards(ctx: Context<AClaimRewards>) -> Result<()> {
-    let user = ctx.accounts.user.to_account_info();
-    let main_state = ctx.accounts.main_state.to_account_info();
-    let referral_account = &mut ctx.accounts.referral_account;
-    let reward_amount = referral_account.earned_rewards;
+// This function pays earned (vested) rewards of a referral account from main_state
+// Params
+//   main_state - MainState account holding rewards
+//   referral_account - ReferralState to pay out
+//   destination - Address receiving rewards
+//   cur_timestamp - Current time
+// Return
+//   Ok on success, ErrorCode on failure
+//     RewardsClaimEvent is emitted on success
+fn pay_rewards(
+    main_state: &mut Account<MainState>,
+    referral_account: &mut ReferralState,
+    destination: &AccountInfo,
+    cur_timestamp: i64
+) -> Result<()> {
+    referral_account.release_vested_rewards(main_state, cur_timestamp as u64);
+    require!(!referral_account.rewards_expired(cur_timestamp as u64), MaxiFarmError::RewardsExpired);
 
+    let reward_amount = referral_account.earned_rewards;
     require!(reward_amount > 0, MaxiFarmError::NoRewardsAvailable);
 
-    // Transfer earned_rewards (SOL) from main_state to user
-    transfer_lamports(&main_state, &user, reward_amount)?;
+    // Transfer earned_rewards (SOL) from main_state to destination
+    transfer_lamports(&main_state.to_account_info(), destination, reward_amount)?;
 
     // Reset earned rewards
     referral_account.earned_rewards = 0;
+    referral_account.rewards_expiry = 0;
+    referral_account.total_claimed += reward_amount;
+    referral_account.last_claim_time = cur_timestamp as u64;
+    main_state.total_rewards_claimed += reward_amount;
 
     emit!(RewardsClaimEvent {
-        user: user.key(),
+        user: referral_account.user,
+        destination: destination.key(),
         rewards: reward_amount,
-        timestamp: Clock::get()?.unix_timestamp
+        timestamp: cur_timestamp
     });
 
     Ok(())
 }
 
+// This function claims earned rewards of a referral account (by its owner or delegate)
+// Params
+//   ctx - ClaimRewards context
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn claim_rewards(ctx: Context<AClaimRewards>) -> Result<()> {
+    let destination = ctx.accounts.destination.to_account_info();
+
+    pay_rewards(
+        &mut ctx.accounts.main_state,
+        &mut ctx.accounts.referral_account,
+        &destination,
+        Clock::get()?.unix_timestamp
+    )
+}
+
+// This function pays earned rewards of many referral accounts to their claim destinations (permissionless)
+// Params
+//   ctx - ClaimRewardsBatch context
+//     remaining_accounts - Pairs of (ReferralState, claim destination)
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn claim_rewards_batch(ctx: Context<AClaimRewardsBatch>) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        !remaining_accounts.is_empty() && remaining_accounts.chunks_exact(2).remainder().is_empty(),
+        MaxiFarmError::InvalidClaimAccounts
+    );
+
+    let cur_timestamp = Clock::get()?.unix_timestamp;
+    for accounts in remaining_accounts.chunks_exact(2) {
+        let referral_info = &accounts[0];
+        let destination = &accounts[1];
+        require!(
+            referral_info.owner.eq(&crate::ID) && referral_info.is_writable && destination.is_writable,
+            MaxiFarmError::InvalidClaimAccounts
+        );
+
+        let mut data = referral_info.try_borrow_mut_data()?;
+        let mut referral_account = ReferralState::try_deserialize(&mut &data[..])?;
+        let (referral_key, _) = Pubkey::find_program_address(
+            &[ReferralState::PREFIX_SEED, referral_account.user.as_ref()],
+            &crate::ID
+        );
+        require!(
+            referral_info.key.eq(&referral_key) && destination.key.eq(&referral_account.payout_address()),
+            MaxiFarmError::InvalidClaimAccounts
+        );
+
+        // Accounts without claimable rewards are skipped, so that one of them doesn't fail the whole batch
+        referral_account.release_vested_rewards(main_state, cur_timestamp as u64);
+        if referral_account.earned_rewards > 0 && !referral_account.rewards_expired(cur_timestamp as u64) {
+            pay_rewards(main_state, &mut referral_account, destination, cur_timestamp)?;
+        }
+        referral_account.try_serialize(&mut &mut data[..])?;
+    }
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct AClaimRewards<'info> {
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub user: Signer<'info>, // Owner of referral account or its delegate
     
     #[account(
         mut,
@@ -45,8 +124,32 @@ pub struct AClaimRewards<'info> {
     )]
     pub main_state: Box<Account<'info, MainState>>, // MainState account
     
-    #[account(mut)]
-    pub referral_account: Account<'info, ReferralState>,
+    #[account(
+        mut,
+        seeds = [ReferralState::PREFIX_SEED, referral_account.user.as_ref()],
+        bump,
+        constraint = referral_account.user == user.key() || referral_account.claim_delegate == user.key() @ MaxiFarmError::Unauthorised
+    )]
+    pub referral_account: Account<'info, ReferralState>, // Owner's ReferralState
+
+    #[account(
+        mut,
+        address = referral_account.payout_address() @ MaxiFarmError::InvalidClaimAccounts
+    )]
+    /// CHECK: this should be set by owner of referral account
+    pub destination: AccountInfo<'info>, // Address receiving rewards
     
     pub system_program: Program<'info, System>
 }
+
+#[derive(Accounts)]
+pub struct AClaimRewardsBatch<'info> {
+    pub cranker: Signer<'info>, // Anyone
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump
+    )]
+    pub main_state: Box<Account<'info, MainState>>, // MainState account
+}
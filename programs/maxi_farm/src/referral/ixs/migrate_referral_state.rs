@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::{
+    utils::realloc_account,
+    ReferralState,
+};
+
+// This function migrates a referral account to the current layout version
+// Params
+//   ctx - ReferralState migration context
+//   user - Owner of the referral account
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn migrate_referral_state(ctx: Context<AMigrateReferralState>, user: Pubkey) -> Result<()> {
+    let referral_info = ctx.accounts.referral_account.to_account_info();
+
+    // New members are zero-initialized
+    realloc_account(
+        referral_info.clone(),
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        8 + ReferralState::MAX_SIZE
+    )?;
+
+    let mut data = referral_info.try_borrow_mut_data()?;
+    let mut referral_account = ReferralState::try_deserialize(&mut &data[..])?;
+    if referral_account.version < ReferralState::VERSION {
+        if referral_account.version == 0 {
+            // Lifetime stats weren't tracked before, so they start from unclaimed rewards
+            referral_account.total_earned = referral_account.earned_rewards;
+        }
+        referral_account.version = ReferralState::VERSION;
+        referral_account.try_serialize(&mut &mut data[..])?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct AMigrateReferralState<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>, // Payer of additional rent
+
+    #[account(
+        mut,
+        seeds = [ReferralState::PREFIX_SEED, user.as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    /// CHECK: deserialized after migration
+    pub referral_account: UncheckedAccount<'info>, // ReferralState account in the previous layout
+
+    pub system_program: Program<'info, System>
+}
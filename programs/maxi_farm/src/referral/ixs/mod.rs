@@ -3,3 +3,15 @@ pub use register::*;
 
 pub mod claim;
 pub use claim::*;
+
+pub mod set_custom_reward;
+pub use set_custom_reward::*;
+
+pub mod migrate_referral_state;
+pub use migrate_referral_state::*;
+
+pub mod set_claim_config;
+pub use set_claim_config::*;
+
+pub mod sweep_expired_rewards;
+pub use sweep_expired_rewards::*;
@@ -12,19 +12,31 @@ pub fn register_user(
     let referral_account = &mut ctx.accounts.referral_account;
 
     referral_account.user = ctx.accounts.user.key();
+    referral_account.version = ReferralState::VERSION;
 
     if let Some(referrer_key) = referrer {
+        let old_referrer = referral_account.referrer;
+        let is_new_referee = old_referrer != referrer_key;
+        // Previous referrer loses the referee on switching referrers
+        if is_new_referee && old_referrer != Pubkey::default() {
+            let old_referrer_account = ctx.accounts.old_referrer_account.as_mut().ok_or(MaxiFarmError::InvalidReferrer)?;
+            old_referrer_account.referee_count = old_referrer_account.referee_count.saturating_sub(1);
+        }
         referral_account.referrer = referrer_key;
 
-        if let Some(referrer_account) = &mut ctx.accounts.referrer_account {
-            if referrer_account.user == Pubkey::default() {
-                referrer_account.user = referrer_key;
-            }
+        // Referrer's account is required to keep its referee count
+        let referrer_account = ctx.accounts.referrer_account.as_mut().ok_or(MaxiFarmError::InvalidReferrer)?;
+        if referrer_account.user == Pubkey::default() {
+            referrer_account.user = referrer_key;
+            referrer_account.version = ReferralState::VERSION;
+        }
+        if is_new_referee {
+            referrer_account.referee_count += 1;
         }
     }
 
     emit!(UserRegisteredEvent {
-        referree: referral_account.user.clone(),
+        referree: referral_account.user,
         referrer: referrer.unwrap(),
         timestamp: Clock::get()?.unix_timestamp
     });
@@ -55,6 +67,13 @@ pub struct ARegisterUser<'info> {
         space = 8 + ReferralState::MAX_SIZE
     )]
     pub referrer_account: Option<Account<'info, ReferralState>>,
+
+    #[account(
+        mut,
+        seeds = [ReferralState::PREFIX_SEED, referral_account.referrer.as_ref()],
+        bump
+    )]
+    pub old_referrer_account: Option<Account<'info, ReferralState>>, // Previous referrer's ReferralState (switching referrers only)
     
     pub system_program: Program<'info, System>
 }
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::{
+    ReferralState,
+    ClaimConfigUpdatedEvent,
+};
+
+// This function sets who can claim rewards on behalf of user and where rewards are paid
+// Params
+//   ctx - SetClaimConfig context
+//   delegate - Address allowed to claim on behalf of user (default pubkey for none)
+//   destination - Address receiving claimed rewards (default pubkey for user)
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn set_claim_config(ctx: Context<ASetClaimConfig>, delegate: Pubkey, destination: Pubkey) -> Result<()> {
+    let referral_account = &mut ctx.accounts.referral_account;
+
+    referral_account.claim_delegate = delegate;
+    referral_account.claim_destination = destination;
+
+    emit!(ClaimConfigUpdatedEvent {
+        user: referral_account.user,
+        delegate,
+        destination,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetClaimConfig<'info> {
+    pub user: Signer<'info>, // Owner of referral account
+
+    #[account(
+        mut,
+        seeds = [ReferralState::PREFIX_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub referral_account: Account<'info, ReferralState>, // User's ReferralState
+}
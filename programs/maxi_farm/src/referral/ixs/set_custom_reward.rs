@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::MAX_TIER1_REWARD,
+    error::MaxiFarmError,
+    MainState, ReferralState,
+    CustomRewardUpdatedEvent,
+};
+
+// This function sets custom tier1 reward of a referrer (for KOL/partner deals)
+// Params
+//   ctx - SetCustomReward context
+//   referrer - Referrer wallet address
+//   tier1_reward - Custom tier1 reward percent (0 to use global tier1 reward)
+//   expiry - Expiry time of custom tier1 reward (0 for no expiry)
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn set_custom_reward(
+    ctx: Context<ASetCustomReward>,
+    referrer: Pubkey,
+    tier1_reward: u64,
+    expiry: u64
+) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    let cur_timestamp = Clock::get()?.unix_timestamp;
+
+    if tier1_reward > 0 {
+        // Tier2/Tier3 rewards are taken from tier1 reward, and total rewards must stay capped
+        require!(
+            tier1_reward.ge(&(main_state.tier2_reward + main_state.tier3_reward)) && tier1_reward.le(&MAX_TIER1_REWARD),
+            MaxiFarmError::InvalidReferralReward
+        );
+        require!(expiry == 0 || expiry > cur_timestamp as u64, MaxiFarmError::InvalidExpiry);
+    }
+
+    let referral_account = &mut ctx.accounts.referral_account;
+    referral_account.custom_tier1_reward = tier1_reward;
+    referral_account.custom_reward_expiry = if tier1_reward > 0 { expiry } else { 0 };
+
+    emit!(CustomRewardUpdatedEvent {
+        authority: ctx.accounts.authority.key(),
+        referrer,
+        tier1_reward: referral_account.custom_tier1_reward,
+        expiry: referral_account.custom_reward_expiry,
+        timestamp: cur_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(referrer: Pubkey)]
+pub struct ASetCustomReward<'info> {
+    pub authority: Signer<'info>, // Owner or referral manager
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+        constraint = authority.key() == main_state.owner || authority.key() == main_state.referral_manager @ MaxiFarmError::Unauthorised
+    )]
+    pub main_state: Box<Account<'info, MainState>>, // MainState account
+
+    #[account(
+        mut,
+        seeds = [ReferralState::PREFIX_SEED, referrer.as_ref()],
+        bump
+    )]
+    pub referral_account: Account<'info, ReferralState>, // Referrer's ReferralState
+}
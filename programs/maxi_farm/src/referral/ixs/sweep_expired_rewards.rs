@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::{
+    error::MaxiFarmError,
+    utils::transfer_lamports,
+    MainState, ReferralState,
+    RewardsSweptEvent,
+};
+
+// This function sweeps expired rewards of a referral account back to the protocol (fee recipient)
+// Params
+//   ctx - SweepExpiredRewards context
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn sweep_expired_rewards(ctx: Context<ASweepExpiredRewards>) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    let referral_account = &mut ctx.accounts.referral_account;
+    let cur_timestamp = Clock::get()?.unix_timestamp;
+
+    referral_account.release_vested_rewards(main_state, cur_timestamp as u64);
+    require!(referral_account.rewards_expired(cur_timestamp as u64), MaxiFarmError::RewardsNotExpired);
+
+    let reward_amount = referral_account.earned_rewards;
+    require!(reward_amount > 0, MaxiFarmError::NoRewardsAvailable);
+
+    // Transfer expired rewards (SOL) from main_state to fee recipient
+    transfer_lamports(&main_state.to_account_info(), &ctx.accounts.fee_recipient, reward_amount)?;
+
+    referral_account.earned_rewards = 0;
+    referral_account.rewards_expiry = 0;
+    main_state.total_rewards_swept += reward_amount;
+
+    emit!(RewardsSweptEvent {
+        authority: ctx.accounts.authority.key(),
+        user: referral_account.user,
+        rewards: reward_amount,
+        timestamp: cur_timestamp
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASweepExpiredRewards<'info> {
+    pub authority: Signer<'info>, // Owner or referral manager
+
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+        constraint = authority.key() == main_state.owner || authority.key() == main_state.referral_manager @ MaxiFarmError::Unauthorised
+    )]
+    pub main_state: Box<Account<'info, MainState>>, // MainState account
+
+    #[account(
+        mut,
+        address = main_state.fee_recipient
+    )]
+    /// CHECK: this should be set by owner
+    pub fee_recipient: AccountInfo<'info>, // FeeRecipient
+
+    #[account(
+        mut,
+        seeds = [ReferralState::PREFIX_SEED, referral_account.user.as_ref()],
+        bump
+    )]
+    pub referral_account: Account<'info, ReferralState>, // ReferralState holding expired rewards
+}
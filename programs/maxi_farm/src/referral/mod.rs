@@ -6,3 +6,6 @@ pub use state::*;
 
 pub mod event;
 pub use event::*;
+
+pub mod rewards;
+pub use rewards::*;
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::BPS,
+    error::MaxiFarmError,
+    MainState, ReferralState
+};
+
+// Referral rewards credited on a trade
+#[derive(Default, Clone, Copy)]
+pub struct ReferralRewards {
+    pub tier1_referrer: Pubkey, // Tier1 referrer
+    pub tier1_reward: u64,      // Tier1 reward
+    pub tier2_referrer: Pubkey, // Tier2 referrer
+    pub tier2_reward: u64,      // Tier2 reward
+    pub tier3_referrer: Pubkey, // Tier3 referrer
+    pub tier3_reward: u64,      // Tier3 reward
+}
+
+impl ReferralRewards {
+    // This function returns sum of rewards credited to all tiers
+    pub fn total(&self) -> u64 {
+        self.tier1_reward + self.tier2_reward + self.tier3_reward
+    }
+}
+
+// This function returns reward percent taken from the trading fee for referrers
+// Params
+//   main_state - MainState holding global rewards
+//   tier1_referral - Tier1 referrer's ReferralState
+//   cur_timestamp - Current time
+// Return
+//   Reward percent (0 if there is no tier1 referrer)
+pub fn referral_reward_rate(
+    main_state: &MainState,
+    tier1_referral: &Option<Box<Account<ReferralState>>>,
+    cur_timestamp: u64
+) -> u64 {
+    if let Some(tier1_referral) = tier1_referral {
+        tier1_referral.tier1_reward(main_state, cur_timestamp)
+    } else {
+        0
+    }
+}
+
+// This function checks whether the trader was referred
+// Params
+//   trader_referral - Trader's ReferralState
+//   tier1_referral - Tier1 referrer's ReferralState
+// Return
+//   true if the trader has a referrer, else false
+//   ErrorCode if tier1 referral is not the trader's referrer
+pub fn check_referee(
+    trader_referral: &Option<Box<Account<ReferralState>>>,
+    tier1_referral: &Option<Box<Account<ReferralState>>>
+) -> Result<bool> {
+    if let Some(trader_referral) = trader_referral {
+        if trader_referral.referrer != Pubkey::default() {
+            if let Some(tier1_referral) = tier1_referral {
+                require!(tier1_referral.user.eq(&trader_referral.referrer), MaxiFarmError::InvalidReferrer);
+            }
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// This function divides referral rewards from the trading fee and credits them to each tier
+// Params
+//   main_state - MainState holding global rewards
+//   fee - Trading fee
+//   volume - Traded amount of SOL
+//   tier1_referral, tier2_referral, tier3_referral - Referrers' ReferralStates
+//   cur_timestamp - Current time
+// Return
+//   Credited referral rewards
+pub fn distribute_referral_rewards(
+    main_state: &MainState,
+    fee: u64,
+    volume: u64,
+    tier1_referral: &mut Option<Box<Account<ReferralState>>>,
+    tier2_referral: &mut Option<Box<Account<ReferralState>>>,
+    tier3_referral: &mut Option<Box<Account<ReferralState>>>,
+    cur_timestamp: u64
+) -> ReferralRewards {
+    let mut rewards = ReferralRewards::default();
+    let tier1_rate = referral_reward_rate(main_state, tier1_referral, cur_timestamp);
+
+    if let Some(tier3_referral) = tier3_referral {
+        rewards.tier3_referrer = tier3_referral.user;
+        rewards.tier3_reward = fee * main_state.tier3_reward / (100 * BPS);
+        tier3_referral.credit_rewards(main_state, rewards.tier3_reward, cur_timestamp);
+        tier3_referral.tier3_volume += volume;
+
+        if let Some(tier2_referral) = tier2_referral { // If tier2 referrer exists, divide rewards
+            rewards.tier2_referrer = tier2_referral.user;
+            rewards.tier2_reward = fee * main_state.tier2_reward / (100 * BPS);
+            tier2_referral.credit_rewards(main_state, rewards.tier2_reward, cur_timestamp);
+            tier2_referral.tier2_volume += volume;
+        }
+        if let Some(tier1_referral) = tier1_referral {
+            rewards.tier1_referrer = tier1_referral.user;
+            rewards.tier1_reward = fee * (tier1_rate - main_state.tier2_reward - main_state.tier3_reward) / (100 * BPS);
+            tier1_referral.credit_rewards(main_state, rewards.tier1_reward, cur_timestamp);
+            tier1_referral.tier1_volume += volume;
+        }
+    } else if let Some(tier2_referral) = tier2_referral { // If tier2 referrer exists, divide rewards
+        rewards.tier2_referrer = tier2_referral.user;
+        rewards.tier2_reward = fee * main_state.tier2_reward / (100 * BPS);
+        tier2_referral.credit_rewards(main_state, rewards.tier2_reward, cur_timestamp);
+        tier2_referral.tier2_volume += volume;
+
+        if let Some(tier1_referral) = tier1_referral {
+            rewards.tier1_referrer = tier1_referral.user;
+            rewards.tier1_reward = fee * (tier1_rate - main_state.tier2_reward) / (100 * BPS);
+            tier1_referral.credit_rewards(main_state, rewards.tier1_reward, cur_timestamp);
+            tier1_referral.tier1_volume += volume;
+        }
+    } else if let Some(tier1_referral) = tier1_referral {
+        rewards.tier1_referrer = tier1_referral.user;
+        rewards.tier1_reward = fee * tier1_rate / (100 * BPS);
+        tier1_referral.credit_rewards(main_state, rewards.tier1_reward, cur_timestamp);
+        tier1_referral.tier1_volume += volume;
+    }
+
+    rewards
+}
@@ -1,14 +1,142 @@
 use anchor_lang::prelude::*;
+use crate::{
+    constants::MAX_TIER1_REWARD,
+    MainState
+};
 
 // Referral state
 #[account]
 pub struct ReferralState {
     pub user: Pubkey, // User's public key
-    pub earned_rewards: u64, // Accumulated referral rewards
+    pub earned_rewards: u64, // Accumulated (claimable) referral rewards
     pub referrer: Pubkey, // Direct referrer (Tier 1)
+    pub custom_tier1_reward: u64, // Custom tier1 reward percent for partner deals (0: global tier1 reward)
+    pub custom_reward_expiry: u64, // Expiry time of custom tier1 reward (0: never expires)
+
+    pub version: u8, // Layout version (0: not migrated yet)
+    pub referee_count: u64, // Number of direct referees
+    pub total_earned: u64, // Lifetime earned rewards
+    pub total_claimed: u64, // Lifetime claimed rewards
+    pub last_claim_time: u64, // Last claimed time
+    pub tier1_volume: u64, // Trading volume (SOL) referred as tier1 referrer
+    pub tier2_volume: u64, // Trading volume (SOL) referred as tier2 referrer
+    pub tier3_volume: u64, // Trading volume (SOL) referred as tier3 referrer
+
+    pub claim_delegate: Pubkey, // Address allowed to claim on behalf of user (default: none)
+    pub claim_destination: Pubkey, // Address receiving claimed rewards (default: user)
+
+    pub locked_rewards: u64, // Rewards still vesting
+    pub vesting_start: u64, // Time locked rewards started vesting from
+    pub vesting_end: u64, // Time locked rewards are fully vested
+    pub rewards_expiry: u64, // Time claimable rewards expire (0: never expires)
 }
 
 impl ReferralState {
     pub const MAX_SIZE: usize = std::mem::size_of::<Self>();    // Size of ReferralState
     pub const PREFIX_SEED: &'static [u8] = b"referral";             // Seed of ReferralState
+    pub const VERSION: u8 = 3;                                      // Current layout version
+
+    // This function returns tier1 reward percent applied when this user is the tier1 referrer
+    // Params
+    //   self - ReferralState struct itself
+    //   main_state - MainState holding global rewards
+    //   cur_timestamp - Current time
+    // Return
+    //   Custom tier1 reward if set and not expired, else global tier1 reward
+    pub fn tier1_reward(&self, main_state: &MainState, cur_timestamp: u64) -> u64 {
+        if self.custom_tier1_reward > 0
+            && (self.custom_reward_expiry == 0 || cur_timestamp < self.custom_reward_expiry) {
+            return self.custom_tier1_reward.min(MAX_TIER1_REWARD);
+        }
+        main_state.tier1_reward
+    }
+
+    // This function credits referral rewards (locked if reward vesting is enabled)
+    // Params
+    //   self - ReferralState struct itself
+    //   main_state - MainState holding reward vesting/expiry settings
+    //   rewards - Amount of rewards
+    //   cur_timestamp - Current time
+    pub fn credit_rewards(&mut self, main_state: &MainState, rewards: u64, cur_timestamp: u64) {
+        self.total_earned += rewards;
+        self.release_vested_rewards(main_state, cur_timestamp);
+
+        if main_state.reward_vesting_duration == 0 {
+            self.add_claimable_rewards(main_state, rewards, cur_timestamp);
+            return;
+        }
+
+        // Locked rewards vest at the sum of both vesting speeds, so that neither of them is delayed
+        //   (Vested part is already released above, so vesting restarts from now)
+        let duration = main_state.reward_vesting_duration as u128;
+        self.vesting_end = if self.locked_rewards == 0 {
+            cur_timestamp + main_state.reward_vesting_duration
+        } else {
+            let locked = self.locked_rewards as u128;
+            let remaining = (self.vesting_end - cur_timestamp) as u128;
+            cur_timestamp + ((locked + rewards as u128) * remaining * duration
+                / (locked * duration + rewards as u128 * remaining)) as u64
+        };
+        self.vesting_start = cur_timestamp;
+        self.locked_rewards += rewards;
+    }
+
+    // This function moves vested part of locked rewards to claimable rewards
+    // Params
+    //   self - ReferralState struct itself
+    //   main_state - MainState holding reward expiry settings
+    //   cur_timestamp - Current time
+    pub fn release_vested_rewards(&mut self, main_state: &MainState, cur_timestamp: u64) {
+        if self.locked_rewards == 0 || cur_timestamp <= self.vesting_start {
+            return;
+        }
+        let vested_rewards = if cur_timestamp >= self.vesting_end {
+            self.locked_rewards
+        } else {
+            (self.locked_rewards as u128 * (cur_timestamp - self.vesting_start) as u128
+                / (self.vesting_end - self.vesting_start) as u128) as u64
+        };
+        self.locked_rewards -= vested_rewards;
+        self.vesting_start = cur_timestamp; // The rest keeps vesting linearly until vesting_end
+        self.add_claimable_rewards(main_state, vested_rewards, cur_timestamp);
+    }
+
+    // This function adds claimable rewards
+    //   Claimable rewards expire together, reward_expiry_period after the latest ones are added
+    // Params
+    //   self - ReferralState struct itself
+    //   main_state - MainState holding reward expiry settings
+    //   rewards - Amount of rewards
+    //   cur_timestamp - Current time
+    fn add_claimable_rewards(&mut self, main_state: &MainState, rewards: u64, cur_timestamp: u64) {
+        if rewards == 0 {
+            return;
+        }
+        if main_state.reward_expiry_period > 0 {
+            self.rewards_expiry = cur_timestamp + main_state.reward_expiry_period;
+        }
+        self.earned_rewards += rewards;
+    }
+
+    // This function returns whether claimable rewards are expired
+    // Params
+    //   self - ReferralState struct itself
+    //   cur_timestamp - Current time
+    // Return
+    //   true if expired, else false
+    pub fn rewards_expired(&self, cur_timestamp: u64) -> bool {
+        self.rewards_expiry != 0 && cur_timestamp >= self.rewards_expiry
+    }
+
+    // This function returns the address receiving claimed rewards
+    // Params
+    //   self - ReferralState struct itself
+    // Return
+    //   Claim destination if set, else user
+    pub fn payout_address(&self) -> Pubkey {
+        if self.claim_destination == Pubkey::default() {
+            return self.user;
+        }
+        self.claim_destination
+    }
 }
@@ -3,7 +3,13 @@ use anchor_lang::{
     solana_program::program::invoke
 };
 use anchor_spl::{
-    token_2022::{self, CloseAccount, SyncNative},
+    token_2022::{
+        self, CloseAccount, SyncNative,
+        spl_token_2022::{
+            extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+            state::Mint as SplMint
+        }
+    },
     token_interface::TokenAccount
 };
 use crate::{
@@ -57,6 +63,43 @@ pub fn calculate_total_amount(fee: u64, input_amount: u64) -> u64 {
         .unwrap() as u64
 }
 
+// This function calculates Token-2022 transfer fee withheld on transferring tokens
+// Params
+//   mint - Token mint account
+//   amount - Amount of tokens sent
+// Return
+//   Transfer fee (0 if the mint has no transfer fee extension)
+pub fn calculate_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let mint_data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<SplMint>::unpack(&mint_data)?;
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => Ok(
+            config.calculate_epoch_fee(Clock::get()?.epoch, amount)
+                .ok_or(MaxiFarmError::InvalidTax)?
+        ),
+        Err(_) => Ok(0)
+    }
+}
+
+// This function calculates amount of tokens to send so that the recipient receives the given amount
+// Params
+//   mint - Token mint account
+//   post_fee_amount - Amount of tokens to be received
+// Return
+//   Amount of tokens to send (transfer fee included)
+pub fn calculate_pre_fee_amount(mint: &AccountInfo, post_fee_amount: u64) -> Result<u64> {
+    let mint_data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<SplMint>::unpack(&mint_data)?;
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => {
+            let fee = config.calculate_inverse_epoch_fee(Clock::get()?.epoch, post_fee_amount)
+                .ok_or(MaxiFarmError::InvalidTax)?;
+            Ok(post_fee_amount + fee)
+        },
+        Err(_) => Ok(post_fee_amount)
+    }
+}
+
 // This function converts required amount of owner's SOL to WSOL if insufficient
 // Params
 //   owner - Owner
@@ -140,3 +183,110 @@ pub fn transfer_lamports(
     **to_account.try_borrow_mut_lamports()? += amount_of_lamports;
     Ok(())
 }
+
+// This function grows a program-owned account to the given size (new bytes are zeroed)
+// Params
+//   account - Account to resize
+//   payer - Payer of additional rent
+//   system_program - System program
+//   new_len - New data length of the account
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn realloc_account<'a>(
+    account: AccountInfo<'a>,
+    payer: AccountInfo<'a>,
+    system_program: AccountInfo<'a>,
+    new_len: usize
+) -> Result<()> {
+    if account.data_len() >= new_len {
+        return Ok(());
+    }
+    let required_lamports = Rent::get()?.minimum_balance(new_len);
+    let cur_lamports = account.lamports();
+    if required_lamports > cur_lamports {
+        invoke(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                payer.key,
+                account.key,
+                required_lamports - cur_lamports
+            ),
+            &[
+                payer,
+                account.clone(),
+                system_program
+            ]
+        )?;
+    }
+    account.realloc(new_len, true)?;
+    Ok(())
+}
+
+// This function creates a program-owned PDA account (works even if the address was pre-funded)
+// Params
+//   payer - Payer of rent
+//   account - PDA account to create
+//   system_program - System program
+//   space - Data length of the account
+//   signer_seeds - Seeds of the PDA (bump included)
+// Return
+//   Ok on success, ErrorCode on failure
+pub fn create_pda_account<'a>(
+    payer: AccountInfo<'a>,
+    account: AccountInfo<'a>,
+    system_program: AccountInfo<'a>,
+    space: usize,
+    signer_seeds: &[&[u8]]
+) -> Result<()> {
+    let required_lamports = Rent::get()?.minimum_balance(space);
+    let cur_lamports = account.lamports();
+    if cur_lamports == 0 {
+        anchor_lang::system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program,
+                anchor_lang::system_program::CreateAccount {
+                    from: payer,
+                    to: account
+                },
+                &[signer_seeds]
+            ),
+            required_lamports,
+            space as u64,
+            &crate::ID
+        )?;
+        return Ok(());
+    }
+
+    if required_lamports > cur_lamports {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer,
+                    to: account.clone()
+                }
+            ),
+            required_lamports - cur_lamports
+        )?;
+    }
+    anchor_lang::system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            anchor_lang::system_program::Allocate {
+                account_to_allocate: account.clone()
+            },
+            &[signer_seeds]
+        ),
+        space as u64
+    )?;
+    anchor_lang::system_program::assign(
+        CpiContext::new_with_signer(
+            system_program,
+            anchor_lang::system_program::Assign {
+                account_to_assign: account
+            },
+            &[signer_seeds]
+        ),
+        &crate::ID
+    )?;
+    Ok(())
+}
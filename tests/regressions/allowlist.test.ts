@@ -0,0 +1,65 @@
+import { web3 } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import {
+  program, mainState, creator, trader, referrer, stranger, poolStateOf, allocationOf,
+  setup, createPoolMint, createPool, buy, expectError, allowlistTree,
+} from "./helper";
+
+describe("private sale allowlist", () => {
+  const allocation = web3.LAMPORTS_PER_SOL
+  const tree = allowlistTree([
+    { wallet: trader.publicKey, allocation },
+    { wallet: referrer.publicKey, allocation: allocation / 2 },
+    { wallet: creator.publicKey, allocation: allocation / 4 },
+  ])
+  let mint: web3.PublicKey
+
+  const buyWithProof = (buyer: web3.Keypair, auth: any, lamports: number) =>
+    buy(buyer, mint, lamports, { auth, privSaleAllocation: allocationOf(mint, buyer.publicKey, 0) })
+
+  before(async () => {
+    await setup()
+    mint = await createPoolMint(6, 1e15)
+    // Pool trades at once, so the root can only be set on creation
+    await createPool(mint, { privSalePeriod: 60 * 60, allowlistRoot: tree.root })
+  })
+
+  it("buys with a valid proof and tracks used allocation", async () => {
+    await buyWithProof(trader, tree.auth(0), allocation * 3 / 5)
+
+    const used = await program.account.privSaleAllocation.fetch(allocationOf(mint, trader.publicKey, 0))
+    assert.isTrue(used.trader.equals(trader.publicKey))
+    assert.equal(used.usedAmount.toNumber(), allocation * 3 / 5)
+  })
+
+  it("rejects buying above the allocation", async () => {
+    await expectError(buyWithProof(trader, tree.auth(0), allocation * 3 / 5), "ExceededAllocation")
+  })
+
+  it("accepts proofs of a leaf without sibling", async () => {
+    await buyWithProof(creator, tree.auth(2), allocation / 4)
+  })
+
+  it("rejects a proof with a changed allocation", async () => {
+    await expectError(buyWithProof(referrer, tree.auth(1, allocation), allocation / 4), "InvalidAllowlistProof")
+  })
+
+  it("rejects a proof of another wallet", async () => {
+    await expectError(buyWithProof(stranger, tree.auth(1), allocation / 4), "InvalidAllowlistProof")
+  })
+
+  it("rejects buying without the allocation account", async () => {
+    await expectError(buy(referrer, mint, allocation / 4, { auth: tree.auth(1) }), "MissingAllocationAccount")
+  })
+
+  it("rejects updating the root once trading started", async () => {
+    await expectError(
+      program.methods
+        .setAllowlistRoot(Array.from(tree.root))
+        .accounts({ authority: creator.publicKey, mainState, poolState: poolStateOf(mint), baseMint: mint })
+        .signers([creator])
+        .rpc(),
+      "TradingStarted"
+    )
+  })
+})
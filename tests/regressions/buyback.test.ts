@@ -0,0 +1,69 @@
+import { web3, BN } from "@coral-xyz/anchor";
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, getMint } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  BPS, program, connection, owner, creator, trader, stranger,
+  mainState, eventAuthority, buybackVault, poolStateOf, ataOf,
+  setup, createPoolMint, createPool, buy, expectError,
+} from "./helper";
+
+describe("buyback and burn", () => {
+  let mint: web3.PublicKey
+
+  before(async () => {
+    await setup()
+    mint = await createPoolMint(6, 1e15)
+    await createPool(mint)
+    await program.methods
+      .setBuybackConfig(new BN(50 * BPS), mint, new BN(0), new BN(5 * BPS))
+      .accounts({ owner, mainState, buybackVault, systemProgram: web3.SystemProgram.programId })
+      .rpc()
+  })
+
+  after(async () => {
+    await program.methods
+      .setBuybackConfig(new BN(0), web3.PublicKey.default, new BN(0), new BN(0))
+      .accounts({ owner, mainState, buybackVault, systemProgram: web3.SystemProgram.programId })
+      .rpc()
+  })
+
+  const executeBuyback = (cranker: web3.Keypair) => program.methods
+    .executeBuyback(new BN(0))
+    .accounts({
+      cranker: cranker.publicKey,
+      mainState,
+      poolState: poolStateOf(mint),
+      poolOwner: creator.publicKey,
+      baseMint: mint,
+      buybackVault,
+      vaultBaseAta: ataOf(mint, buybackVault),
+      reserverBaseAta: ataOf(mint, poolStateOf(mint)),
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: web3.SystemProgram.programId,
+      eventAuthority,
+      program: program.programId,
+    })
+    .signers([cranker])
+    .rpc()
+
+  it("sends buyback share of trading fee to the vault", async () => {
+    const vaultBalance = await connection.getBalance(buybackVault)
+    await buy(trader, mint, web3.LAMPORTS_PER_SOL, { buybackVault: true })
+    assert.isAbove(await connection.getBalance(buybackVault), vaultBalance)
+  })
+
+  it("rejects trades without the vault while buyback is enabled", async () => {
+    await expectError(buy(trader, mint, web3.LAMPORTS_PER_SOL), "MissingBuybackVault")
+  })
+
+  it("lets anyone buy back and burn tokens with vault's SOL", async () => {
+    const supply = (await getMint(connection, mint, undefined, TOKEN_2022_PROGRAM_ID)).supply
+    const tradeCount = (await program.account.poolState.fetch(poolStateOf(mint))).tradeCount.toNumber()
+    await executeBuyback(stranger)
+
+    assert.isBelow(Number((await getMint(connection, mint, undefined, TOKEN_2022_PROGRAM_ID)).supply), Number(supply))
+    assert.equal((await program.account.poolState.fetch(poolStateOf(mint))).tradeCount.toNumber(), tradeCount + 1)
+    assert.equal(await connection.getBalance(buybackVault), await connection.getMinimumBalanceForRentExemption(0))
+  })
+})
@@ -0,0 +1,113 @@
+import { web3 } from "@coral-xyz/anchor";
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, getAccount } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  program, connection, owner, creator, trader, mainState, eventAuthority, poolStateOf, ataOf,
+  setup, createPoolMint, createPool, buy, updateMainState, expectError,
+} from "./helper";
+
+describe("pool cancellation and closing", () => {
+  const supply = 1e15
+  let oldWithdrawer: web3.PublicKey
+
+  const cancelPool = (mint: web3.PublicKey) => program.methods
+    .cancelPool()
+    .accounts({
+      owner: creator.publicKey,
+      poolState: poolStateOf(mint),
+      baseMint: mint,
+      reserverBaseAta: ataOf(mint, poolStateOf(mint)),
+      ownerBaseAta: ataOf(mint, creator.publicKey),
+      creatorVesting: null,
+      vestingBaseAta: null,
+      beneficiary: null,
+      beneficiaryBaseAta: null,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: web3.SystemProgram.programId,
+    })
+    .signers([creator])
+    .rpc()
+
+  const closePool = (mint: web3.PublicKey) => program.methods
+    .closePool()
+    .accounts({
+      authority: creator.publicKey,
+      mainState,
+      poolState: poolStateOf(mint),
+      poolOwner: creator.publicKey,
+      baseMint: mint,
+      reserverBaseAta: ataOf(mint, poolStateOf(mint)),
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    })
+    .signers([creator])
+    .rpc()
+
+  const withdraw = (mint: web3.PublicKey) => program.methods
+    .withdraw()
+    .accounts({
+      withdrawer: owner,
+      mainState,
+      poolState: poolStateOf(mint),
+      baseMint: mint,
+      reserverBaseAta: ataOf(mint, poolStateOf(mint)),
+      withdrawerBaseAta: ataOf(mint, owner),
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: web3.SystemProgram.programId,
+      eventAuthority,
+      program: program.programId,
+    })
+    .rpc()
+
+  before(async () => {
+    await setup()
+    oldWithdrawer = (await program.account.mainState.fetch(mainState)).withdrawer
+    await updateMainState({ withdrawer: owner })
+  })
+
+  after(async () => {
+    await updateMainState({ withdrawer: oldWithdrawer })
+  })
+
+  it("cancels an untraded pool and returns the supply to its owner", async () => {
+    const mint = await createPoolMint(6, supply)
+    await createPool(mint)
+    await cancelPool(mint)
+
+    assert.isNull(await connection.getAccountInfo(poolStateOf(mint)))
+    assert.isNull(await connection.getAccountInfo(ataOf(mint, poolStateOf(mint))))
+    const ownerAta = await getAccount(connection, ataOf(mint, creator.publicKey), undefined, TOKEN_2022_PROGRAM_ID)
+    assert.equal(Number(ownerAta.amount), supply)
+  })
+
+  it("rejects cancelling a traded pool", async () => {
+    const mint = await createPoolMint(6, supply)
+    await createPool(mint)
+    await buy(trader, mint, web3.LAMPORTS_PER_SOL / 2)
+    await expectError(cancelPool(mint), "PoolAlreadyTraded")
+  })
+
+  it("rejects closing an incomplete pool", async () => {
+    const mint = await createPoolMint(6, supply)
+    await createPool(mint)
+    await expectError(closePool(mint), "BondingCurveIncomplete")
+  })
+
+  it("closes a completed pool once withdrawn and returns rent to its owner", async () => {
+    const mint = await createPoolMint(6, supply)
+    await createPool(mint, { realQuoteThreshold: web3.LAMPORTS_PER_SOL })
+    await buy(trader, mint, 2 * web3.LAMPORTS_PER_SOL)
+    assert.isTrue((await program.account.poolState.fetch(poolStateOf(mint))).complete)
+
+    await expectError(closePool(mint), "BondingCurveNotWithdrawn")
+    await withdraw(mint)
+
+    const ownerBalance = await connection.getBalance(creator.publicKey)
+    await closePool(mint)
+    assert.isNull(await connection.getAccountInfo(poolStateOf(mint)))
+    assert.isNull(await connection.getAccountInfo(ataOf(mint, poolStateOf(mint))))
+    // Owner receives rent of both accounts, more than the fee it paid
+    assert.isAbove(await connection.getBalance(creator.publicKey), ownerBalance)
+  })
+})
@@ -0,0 +1,17 @@
+import { assert } from "chai";
+import { program, poolStateOf, setup, createPoolMint, createPool } from "./helper";
+
+describe("pool creation", () => {
+  before(async () => {
+    await setup()
+  })
+
+  it("creates a pool for a 100M supply mint with 9 decimals", async () => {
+    const supply = 100_000_000 * 1_000_000_000
+    const mint = await createPoolMint(9, supply)
+    await createPool(mint)
+
+    const poolState = await program.account.poolState.fetch(poolStateOf(mint))
+    assert.equal(poolState.realBaseReserves.toString(), supply.toString())
+  })
+})
@@ -0,0 +1,299 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3, BN, Program } from "@coral-xyz/anchor";
+import { MaxiFarm } from "../../target/types/maxi_farm";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  AuthorityType,
+  MINT_SIZE,
+  TOKEN_2022_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotentInstruction,
+  createInitializeMintInstruction,
+  createMintToInstruction,
+  createSetAuthorityInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { keccak_256 } from "@noble/hashes/sha3";
+import { assert } from "chai";
+import { sleep } from "../connectivity/utils";
+
+export { sleep }
+
+export const FEE_PRE_DIV = 1000 // 1000 for 1%
+export const BPS = 100 // 100 for 1%
+
+export const provider = anchor.AnchorProvider.env()
+anchor.setProvider(provider);
+export const program = anchor.workspace.MaxiFarm as Program<MaxiFarm>;
+export const connection = provider.connection
+export const owner = provider.wallet.publicKey // MainState owner
+
+export const creator = web3.Keypair.generate()
+export const trader = web3.Keypair.generate()
+export const referrer = web3.Keypair.generate()
+export const stranger = web3.Keypair.generate()
+
+export const pda = (seeds: (Buffer | Uint8Array)[]) => web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0]
+export const mainState = pda([Buffer.from("main")])
+export const eventAuthority = pda([Buffer.from("__event_authority")])
+export const buybackVault = pda([Buffer.from("buyback")])
+export const poolStateOf = (mint: web3.PublicKey) => pda([Buffer.from("pool"), mint.toBuffer()])
+export const referralOf = (user: web3.PublicKey) => pda([Buffer.from("referral"), user.toBuffer()])
+export const permitNonceOf = (user: web3.PublicKey, nonce: number) => pda([Buffer.from("permit"), user.toBuffer(), new BN(nonce).toArrayLike(Buffer, "le", 8)])
+export const allocationOf = (mint: web3.PublicKey, holder: web3.PublicKey, phase: number) =>
+  pda([Buffer.from("allocation"), poolStateOf(mint).toBuffer(), holder.toBuffer(), Buffer.from([phase])])
+export const ataOf = (mint: web3.PublicKey, authority: web3.PublicKey) =>
+  getAssociatedTokenAddressSync(mint, authority, true, TOKEN_2022_PROGRAM_ID)
+
+let ready: Promise<void> | undefined
+
+// Funds test wallets and initializes MainState (once for all spec files)
+export function setup() {
+  ready = ready ?? (async () => {
+    for (const wallet of [creator, trader, referrer, stranger]) {
+      await connection.requestAirdrop(wallet.publicKey, 100 * web3.LAMPORTS_PER_SOL)
+    }
+    await sleep(3_000)
+
+    if (!(await connection.getAccountInfo(mainState))) {
+      await program.methods.initMainState(owner).accounts({ owner, mainState, systemProgram: web3.SystemProgram.programId }).rpc()
+    }
+  })()
+  return ready
+}
+
+// Creates a non-mintable Token-2022 mint whose whole supply is held by the pool's ATA
+export async function createPoolMint(decimals: number, supply: number) {
+  const mintKeypair = web3.Keypair.generate()
+  const mint = mintKeypair.publicKey
+  const poolAta = ataOf(mint, poolStateOf(mint))
+  const tx = new web3.Transaction().add(
+    web3.SystemProgram.createAccount({
+      fromPubkey: creator.publicKey,
+      newAccountPubkey: mint,
+      lamports: await connection.getMinimumBalanceForRentExemption(MINT_SIZE),
+      space: MINT_SIZE,
+      programId: TOKEN_2022_PROGRAM_ID,
+    }),
+    createInitializeMintInstruction(mint, decimals, creator.publicKey, null, TOKEN_2022_PROGRAM_ID),
+    createAssociatedTokenAccountIdempotentInstruction(creator.publicKey, poolAta, poolStateOf(mint), mint, TOKEN_2022_PROGRAM_ID),
+    createMintToInstruction(mint, poolAta, creator.publicKey, supply, [], TOKEN_2022_PROGRAM_ID),
+    createSetAuthorityInstruction(mint, creator.publicKey, AuthorityType.MintTokens, null, [], TOKEN_2022_PROGRAM_ID),
+  )
+  await web3.sendAndConfirmTransaction(connection, tx, [creator, mintKeypair])
+  return mint
+}
+
+export type PoolOptions = {
+  taxSchedule?: any,
+  privSalePeriod?: number, // Seconds
+  startTime?: number,
+  allowlistRoot?: Buffer,
+  realQuoteThreshold?: number, // Lamports
+}
+
+export async function createPool(mint: web3.PublicKey, opts: PoolOptions = {}) {
+  const { feeRecipient } = await program.account.mainState.fetch(mainState)
+  const privSalePeriod = opts.privSalePeriod ? new BN(opts.privSalePeriod) : null
+  const startTime = opts.startTime ? new BN(opts.startTime) : null
+  await program.methods
+    .createPool("https://example.com/metadata.json", new BN(0), new BN(0), new BN(0), new BN(opts.realQuoteThreshold ?? 80 * web3.LAMPORTS_PER_SOL), 0, privSalePeriod, startTime, null, null, opts.taxSchedule ?? null, opts.allowlistRoot ? Array.from(opts.allowlistRoot) : null)
+    .accounts({
+      creator: creator.publicKey,
+      mainState,
+      poolState: poolStateOf(mint),
+      baseMint: mint,
+      reserverBaseAta: ataOf(mint, poolStateOf(mint)),
+      feeRecipient,
+      buybackVault: null,
+      creatorBaseAta: null,
+      privSaleAllocation: null,
+      creatorVesting: null,
+      vestingBaseAta: null,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: web3.SystemProgram.programId,
+      eventAuthority,
+      program: program.programId,
+    })
+    .signers([creator])
+    .rpc()
+}
+
+export type BuyOptions = {
+  referrer?: web3.PublicKey,
+  buybackVault?: boolean,
+  auth?: any,                             // PrivSaleAuth
+  permitNonce?: web3.PublicKey,
+  privSaleAllocation?: web3.PublicKey,
+  preInstructions?: web3.TransactionInstruction[],
+}
+
+export async function buy(buyer: web3.Keypair, mint: web3.PublicKey, lamports: number, opts: BuyOptions = {}) {
+  const { feeRecipient } = await program.account.mainState.fetch(mainState)
+  const { owner: poolOwner } = await program.account.poolState.fetch(poolStateOf(mint))
+  return await program.methods
+    .buyTokensFromExactSol(new BN(lamports), new BN(0), opts.auth ?? null)
+    .accounts({
+      buyer: buyer.publicKey,
+      mainState,
+      feeRecipient,
+      poolState: poolStateOf(mint),
+      poolOwner,
+      baseMint: mint,
+      buyerBaseAta: ataOf(mint, buyer.publicKey),
+      reserverBaseAta: ataOf(mint, poolStateOf(mint)),
+      buyerReferral: opts.referrer ? referralOf(buyer.publicKey) : null,
+      tier1Referral: opts.referrer ? referralOf(opts.referrer) : null,
+      tier2Referral: null,
+      tier3Referral: null,
+      permitNonce: opts.permitNonce ?? null,
+      privSaleAllocation: opts.privSaleAllocation ?? null,
+      traderStats: null,
+      gateTokenAccount: null,
+      gateMetadata: null,
+      platform: null,
+      buybackVault: opts.buybackVault ? buybackVault : null,
+      ixSysvar: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: web3.SystemProgram.programId,
+      eventAuthority,
+      program: program.programId,
+    })
+    .preInstructions(opts.preInstructions ?? [])
+    .signers([buyer])
+    .rpc()
+}
+
+// Returns current time of the cluster, as read by the program
+export async function chainTime() {
+  // Clock sysvar: slot, epoch_start_timestamp, epoch, leader_schedule_epoch, unix_timestamp (8 bytes each)
+  const clock = await connection.getAccountInfo(web3.SYSVAR_CLOCK_PUBKEY)
+  return Number(clock!.data.readBigInt64LE(32))
+}
+
+// Waits until the cluster reaches the time
+export async function waitUntil(timestamp: number) {
+  while ((await chainTime()) < timestamp) {
+    await sleep(500)
+  }
+}
+
+export type SalePermit = {
+  baseMint: web3.PublicKey,
+  trader: web3.PublicKey,
+  phase: number,
+  isBuy: boolean,
+  maxQuoteAmount: number,
+  expiry: number,
+  nonce: number,
+}
+
+// Serializes a permit as the program does (borsh), which is the signed message
+export function encodePermit(permit: SalePermit) {
+  return Buffer.concat([
+    program.programId.toBuffer(),
+    permit.baseMint.toBuffer(),
+    permit.trader.toBuffer(),
+    Buffer.from([permit.phase, permit.isBuy ? 1 : 0]),
+    new BN(permit.maxQuoteAmount).toArrayLike(Buffer, "le", 8),
+    new BN(permit.expiry).toArrayLike(Buffer, "le", 8),
+    new BN(permit.nonce).toArrayLike(Buffer, "le", 8),
+  ])
+}
+
+// Builds SignedSalePermit argument
+export function signedPermit(permit: SalePermit, sig: Uint8Array) {
+  return {
+    permit: {
+      programId: program.programId,
+      baseMint: permit.baseMint,
+      trader: permit.trader,
+      phase: permit.phase,
+      isBuy: permit.isBuy,
+      maxQuoteAmount: new BN(permit.maxQuoteAmount),
+      expiry: new BN(permit.expiry),
+      nonce: new BN(permit.nonce),
+    },
+    sig: Buffer.from(sig),
+  }
+}
+
+// Signs a permit with an ed25519 key, returning the Ed25519Program instruction and PrivSaleAuth argument
+export function ed25519Permit(signer: web3.Keypair, permit: SalePermit) {
+  const ix = web3.Ed25519Program.createInstructionWithPrivateKey({ privateKey: signer.secretKey, message: encodePermit(permit) })
+  // Ed25519Program data: offsets (2 + 14 bytes), public key (32 bytes), signature (64 bytes), message
+  const sig = ix.data.subarray(48, 112)
+  return { ix, auth: { permit: { "0": signedPermit(permit, sig) } } }
+}
+
+// Merkle tree of an allowlist, hashed as the program does (leaf: keccak256(wallet || allocation as u64 LE), sorted pairs)
+export function allowlistTree(entries: { wallet: web3.PublicKey, allocation: number }[]) {
+  const hashPair = (a: Buffer, b: Buffer) => Buffer.from(keccak_256(Buffer.concat(Buffer.compare(a, b) <= 0 ? [a, b] : [b, a])))
+  const leaves = entries.map(({ wallet, allocation }) =>
+    Buffer.from(keccak_256(Buffer.concat([wallet.toBuffer(), new BN(allocation).toArrayLike(Buffer, "le", 8)]))))
+  const levels = [leaves]
+  while (levels[levels.length - 1].length > 1) {
+    const level = levels[levels.length - 1]
+    const next: Buffer[] = []
+    for (let i = 0; i < level.length; i += 2) {
+      next.push(i + 1 < level.length ? hashPair(level[i], level[i + 1]) : level[i])
+    }
+    levels.push(next)
+  }
+
+  // Builds PrivSaleAuth argument of an entry
+  const auth = (index: number, allocation = entries[index].allocation) => {
+    const proof: number[][] = []
+    for (let level = 0, i = index; level < levels.length - 1; level++, i >>= 1) {
+      const sibling = i ^ 1
+      if (sibling < levels[level].length) {
+        proof.push(Array.from(levels[level][sibling]))
+      }
+    }
+    return { allowlist: { "0": { allocation: new BN(allocation), proof } } }
+  }
+  return { root: levels[levels.length - 1][0], auth }
+}
+
+export async function updateTax(mint: web3.PublicKey, buyTax: number, sellTax: number, taxSchedule: any) {
+  return await program.methods
+    .updateTax(new BN(buyTax), new BN(sellTax), taxSchedule)
+    .accounts({ owner: creator.publicKey, mainState, poolState: poolStateOf(mint), baseMint: mint })
+    .signers([creator])
+    .rpc()
+}
+
+// Updates MainState, keeping all values but the given ones
+export async function updateMainState(changes: Record<string, any>) {
+  const state = await program.account.mainState.fetch(mainState)
+  const input = {
+    signer: state.signer,
+    withdrawer: state.withdrawer,
+    tradingFee: state.tradingFee,
+    feeRecipient: state.feeRecipient,
+    referralManager: state.referralManager,
+    refereeDiscount: state.refereeDiscount,
+    rewardVestingDuration: state.rewardVestingDuration,
+    rewardExpiryPeriod: state.rewardExpiryPeriod,
+    secp256k1Signer: state.secp256k1Signer,
+    maxBuyTax: state.maxBuyTax,
+    maxSellTax: state.maxSellTax,
+    launchFee: state.launchFee,
+    launchFeeDuration: state.launchFeeDuration,
+    feeManager: state.feeManager,
+    ...changes,
+  }
+  await program.methods.updateMainState(input as any).accounts({ owner, mainState }).rpc()
+}
+
+export async function expectError(promise: Promise<any>, code: string) {
+  try {
+    await promise
+  } catch (e) {
+    const errorCode = e instanceof anchor.AnchorError ? e.error.errorCode.code : `${e}`
+    assert.include(errorCode, code)
+    return
+  }
+  assert.fail(`expected ${code}`)
+}
@@ -0,0 +1,103 @@
+import { web3, BN } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import {
+  program, mainState, creator, trader, poolStateOf, allocationOf, permitNonceOf,
+  setup, createPoolMint, createPool, buy, expectError, chainTime, waitUntil, allowlistTree, ed25519Permit,
+} from "./helper";
+
+describe("private sale phases", () => {
+  const phaseSigner = web3.Keypair.generate()
+  const walletCap = web3.LAMPORTS_PER_SOL / 2
+  const tree = allowlistTree([
+    { wallet: trader.publicKey, allocation: web3.LAMPORTS_PER_SOL },
+    { wallet: creator.publicKey, allocation: web3.LAMPORTS_PER_SOL },
+  ])
+  // Allowlisted wallets buy in phase 0 (capped), then permits of the phase signer in phase 1
+  const phases = [
+    { startOffset: new BN(0), signer: web3.PublicKey.default, allowlistRoot: Array.from(tree.root), walletCap: new BN(walletCap), pricePremium: new BN(0) },
+    { startOffset: new BN(15), signer: phaseSigner.publicKey, allowlistRoot: new Array(32).fill(0), walletCap: new BN(0), pricePremium: new BN(0) },
+  ]
+  const privSalePeriod = 25
+  let mint: web3.PublicKey
+  let startTime: number
+
+  const setPhases = (phases: any[]) => program.methods
+    .setPrivSalePhases(phases)
+    .accounts({ authority: creator.publicKey, mainState, poolState: poolStateOf(mint), baseMint: mint })
+    .signers([creator])
+    .rpc()
+
+  const buyWithProof = (lamports: number) =>
+    buy(trader, mint, lamports, { auth: tree.auth(0), privSaleAllocation: allocationOf(mint, trader.publicKey, 0) })
+
+  const buyWithPermit = async (phase: number, nonce: number) => {
+    const { ix, auth } = ed25519Permit(phaseSigner, {
+      baseMint: mint,
+      trader: trader.publicKey,
+      phase,
+      isBuy: true,
+      maxQuoteAmount: web3.LAMPORTS_PER_SOL,
+      expiry: (await chainTime()) + 60 * 60,
+      nonce,
+    })
+    return await buy(trader, mint, web3.LAMPORTS_PER_SOL / 2, { auth, permitNonce: permitNonceOf(trader.publicKey, nonce), preInstructions: [ix] })
+  }
+
+  before(async () => {
+    await setup()
+    mint = await createPoolMint(6, 1e15)
+    startTime = (await chainTime()) + 10
+    await createPool(mint, { privSalePeriod, startTime })
+  })
+
+  it("rejects phases whose first phase doesn't start at trading start", async () => {
+    await expectError(setPhases([{ ...phases[0], startOffset: new BN(1) }]), "InvalidPrivSalePhases")
+  })
+
+  it("rejects phases starting after private sale period", async () => {
+    await expectError(setPhases([phases[0], { ...phases[1], startOffset: new BN(privSalePeriod) }]), "InvalidPrivSalePhases")
+  })
+
+  it("sets phases before trading starts", async () => {
+    await setPhases(phases)
+
+    const poolState = await program.account.poolState.fetch(poolStateOf(mint))
+    assert.equal(poolState.numPrivSalePhases, 2)
+  })
+
+  it("locks phases and allowlist root once trading started", async () => {
+    await waitUntil(startTime)
+    await expectError(setPhases(phases), "TradingStarted")
+    await expectError(
+      program.methods
+        .setAllowlistRoot(Array.from(tree.root))
+        .accounts({ authority: creator.publicKey, mainState, poolState: poolStateOf(mint), baseMint: mint })
+        .signers([creator])
+        .rpc(),
+      "TradingStarted"
+    )
+  })
+
+  it("caps allowlist allocation at phase's wallet cap", async () => {
+    await buyWithProof(walletCap * 4 / 5)
+    await expectError(buyWithProof(walletCap / 2), "ExceededAllocation")
+  })
+
+  it("rejects permits for a later phase", async () => {
+    await expectError(buyWithPermit(1, 1), "InvalidPermit")
+  })
+
+  it("accepts permits of the phase signer in the next phase", async () => {
+    await waitUntil(startTime + phases[1].startOffset.toNumber())
+    await buyWithPermit(1, 2)
+  })
+
+  it("rejects allowlist proofs in a phase without allowlist", async () => {
+    await expectError(buyWithProof(walletCap / 5), "InvalidAllowlistProof")
+  })
+
+  it("opens public trading after the last phase", async () => {
+    await waitUntil(startTime + privSalePeriod)
+    await buy(trader, mint, web3.LAMPORTS_PER_SOL / 2)
+  })
+})
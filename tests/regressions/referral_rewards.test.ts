@@ -0,0 +1,62 @@
+import { web3, BN } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import {
+  program, connection, trader, referrer, referralOf,
+  setup, sleep, createPoolMint, createPool, buy, updateMainState,
+} from "./helper";
+
+describe("referral reward expiry and vesting", () => {
+  const expiryPeriod = 2 * 24 * 60 * 60
+  const vestingDuration = 24 * 60 * 60
+  let mint: web3.PublicKey
+
+  before(async () => {
+    await setup()
+    mint = await createPoolMint(6, 1e15)
+    await createPool(mint)
+    await program.methods
+      .registerUser(referrer.publicKey)
+      .accounts({
+        user: trader.publicKey,
+        referralAccount: referralOf(trader.publicKey),
+        referrerAccount: referralOf(referrer.publicKey),
+        oldReferrerAccount: null,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .signers([trader])
+      .rpc()
+  })
+
+  after(async () => {
+    await updateMainState({ rewardVestingDuration: new BN(0), rewardExpiryPeriod: new BN(0) })
+  })
+
+  it("pushes reward expiry forward on each accrual", async () => {
+    await updateMainState({ rewardVestingDuration: new BN(0), rewardExpiryPeriod: new BN(expiryPeriod) })
+
+    await buy(trader, mint, web3.LAMPORTS_PER_SOL / 2, { referrer: referrer.publicKey })
+    const first = await program.account.referralState.fetch(referralOf(referrer.publicKey))
+    await sleep(2_000)
+    await buy(trader, mint, web3.LAMPORTS_PER_SOL / 2, { referrer: referrer.publicKey })
+    const second = await program.account.referralState.fetch(referralOf(referrer.publicKey))
+
+    assert.isTrue(second.earnedRewards.gt(first.earnedRewards))
+    assert.isTrue(second.rewardsExpiry.gt(first.rewardsExpiry))
+  })
+
+  it("doesn't delay locked rewards on new accruals", async () => {
+    await updateMainState({ rewardVestingDuration: new BN(vestingDuration), rewardExpiryPeriod: new BN(expiryPeriod) })
+
+    await buy(trader, mint, web3.LAMPORTS_PER_SOL / 2, { referrer: referrer.publicKey })
+    const first = await program.account.referralState.fetch(referralOf(referrer.publicKey))
+    await sleep(2_000)
+    await buy(trader, mint, web3.LAMPORTS_PER_SOL / 2, { referrer: referrer.publicKey })
+    const second = await program.account.referralState.fetch(referralOf(referrer.publicKey))
+    const now = (await connection.getBlockTime(await connection.getSlot()))!
+
+    assert.isTrue(second.lockedRewards.gt(first.lockedRewards))
+    // Both accruals vest at their own speeds at least, so everything unlocks within one vesting duration
+    assert.isAtMost(second.vestingEnd.toNumber(), now + vestingDuration)
+    assert.isAbove(second.vestingEnd.toNumber(), now)
+  })
+})
@@ -0,0 +1,90 @@
+import { web3 } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import {
+  program, mainState, trader, stranger, permitNonceOf,
+  setup, createPoolMint, createPool, buy, updateMainState, expectError, chainTime,
+  SalePermit, ed25519Permit,
+} from "./helper";
+
+describe("private sale permits", () => {
+  const signer = web3.Keypair.generate()
+  const maxQuoteAmount = web3.LAMPORTS_PER_SOL
+  let oldSigner: web3.PublicKey
+  let mint: web3.PublicKey
+  let permit: SalePermit
+  let nonce = 1
+
+  const buyWithPermit = (buyer: web3.Keypair, permit: SalePermit, lamports: number, permitSigner = signer) => {
+    const { ix, auth } = ed25519Permit(permitSigner, permit)
+    return buy(buyer, mint, lamports, { auth, permitNonce: permitNonceOf(buyer.publicKey, permit.nonce), preInstructions: [ix] })
+  }
+
+  before(async () => {
+    await setup()
+    oldSigner = (await program.account.mainState.fetch(mainState)).signer
+    await updateMainState({ signer: signer.publicKey })
+    mint = await createPoolMint(6, 1e15)
+    await createPool(mint, { privSalePeriod: 60 * 60 })
+  })
+
+  beforeEach(async () => {
+    permit = {
+      baseMint: mint,
+      trader: trader.publicKey,
+      phase: 0,
+      isBuy: true,
+      maxQuoteAmount,
+      expiry: (await chainTime()) + 60 * 60,
+      nonce: nonce++,
+    }
+  })
+
+  after(async () => {
+    await updateMainState({ signer: oldSigner })
+  })
+
+  it("rejects buying without a permit", async () => {
+    await expectError(buy(trader, mint, maxQuoteAmount), "MissingSignature")
+  })
+
+  it("buys with a permit and records its nonce", async () => {
+    await buyWithPermit(trader, permit, maxQuoteAmount / 2)
+
+    const permitNonce = await program.account.permitNonce.fetch(permitNonceOf(trader.publicKey, permit.nonce))
+    assert.isTrue(permitNonce.trader.equals(trader.publicKey))
+    assert.equal(permitNonce.nonce.toNumber(), permit.nonce)
+  })
+
+  it("rejects replaying a permit", async () => {
+    await buyWithPermit(trader, permit, maxQuoteAmount / 2)
+    await expectError(buyWithPermit(trader, permit, maxQuoteAmount / 4), "PermitAlreadyUsed")
+  })
+
+  it("rejects a permit of another wallet", async () => {
+    await expectError(buyWithPermit(stranger, permit, maxQuoteAmount / 2), "PermitWrongTrader")
+  })
+
+  it("rejects a permit for the other trade direction", async () => {
+    await expectError(buyWithPermit(trader, { ...permit, isBuy: false }, maxQuoteAmount / 2), "InvalidPermit")
+  })
+
+  it("rejects a permit for another pool", async () => {
+    await expectError(buyWithPermit(trader, { ...permit, baseMint: web3.Keypair.generate().publicKey }, maxQuoteAmount / 2), "InvalidPermit")
+  })
+
+  it("rejects a permit for another phase", async () => {
+    await expectError(buyWithPermit(trader, { ...permit, phase: 1 }, maxQuoteAmount / 2), "InvalidPermit")
+  })
+
+  it("rejects an expired permit", async () => {
+    await expectError(buyWithPermit(trader, { ...permit, expiry: (await chainTime()) - 1 }, maxQuoteAmount / 2), "PermitExpired")
+  })
+
+  it("rejects buying above permit's amount", async () => {
+    await expectError(buyWithPermit(trader, permit, maxQuoteAmount + 1), "PermitExceeded")
+  })
+
+  it("rejects a permit signed by another key", async () => {
+    await expectError(buyWithPermit(trader, permit, maxQuoteAmount / 2, web3.Keypair.generate()), "SigVerificationFailed")
+  })
+})
@@ -0,0 +1,163 @@
+import { web3 } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import {
+  program, mainState, trader, permitNonceOf,
+  setup, createPoolMint, createPool, buy, updateMainState, expectError, chainTime,
+  SalePermit, encodePermit, signedPermit, ed25519Permit,
+} from "./helper";
+
+// Builds an Ed25519Program instruction verifying several signatures, all held in its own data
+function ed25519MultiSigInstruction(entries: { signer: web3.Keypair, message: Buffer }[]) {
+  const offsetsLen = 2 + entries.length * 14
+  const offsets = Buffer.alloc(offsetsLen)
+  offsets.writeUInt8(entries.length, 0)
+  const payloads: Buffer[] = []
+  let dataOffset = offsetsLen
+  entries.forEach(({ signer, message }, i) => {
+    // Ed25519Program data of a single signature: offsets (2 + 14 bytes), public key (32 bytes), signature (64 bytes), message
+    const single = web3.Ed25519Program.createInstructionWithPrivateKey({ privateKey: signer.secretKey, message })
+    const payload = single.data.subarray(16)
+    const pos = 2 + i * 14
+    offsets.writeUInt16LE(dataOffset + 32, pos)       // signature_offset
+    offsets.writeUInt16LE(0xffff, pos + 2)            // signature_instruction_index
+    offsets.writeUInt16LE(dataOffset, pos + 4)        // public_key_offset
+    offsets.writeUInt16LE(0xffff, pos + 6)            // public_key_instruction_index
+    offsets.writeUInt16LE(dataOffset + 96, pos + 8)   // message_data_offset
+    offsets.writeUInt16LE(message.length, pos + 10)   // message_data_size
+    offsets.writeUInt16LE(0xffff, pos + 12)           // message_instruction_index
+    payloads.push(payload)
+    dataOffset += payload.length
+  })
+  return new web3.TransactionInstruction({
+    keys: [],
+    programId: web3.Ed25519Program.programId,
+    data: Buffer.concat([offsets, ...payloads]),
+  })
+}
+
+// Signs a permit with a secp256k1 key, returning the Secp256k1Program instruction and PrivSaleAuth argument
+function secp256k1Permit(privateKey: Uint8Array, permit: SalePermit, instructionIndex: number) {
+  const ix = web3.Secp256k1Program.createInstructionWithPrivateKey({ privateKey, message: encodePermit(permit), instructionIndex })
+  // Secp256k1Program data: offsets (1 + 11 bytes), eth address (20 bytes), signature (64 bytes), recovery id, message
+  const sig = ix.data.subarray(32, 97)
+  return { ix, auth: { secp256k1Permit: { "0": signedPermit(permit, sig) } } }
+}
+
+// Returns eth address of a secp256k1 key
+function ethAddressOf(privateKey: Uint8Array) {
+  const { data } = web3.Secp256k1Program.createInstructionWithPrivateKey({ privateKey, message: Buffer.alloc(0) })
+  return Array.from(data.subarray(12, 32))
+}
+
+// Random secp256k1 private key (32 random bytes)
+const secp256k1Key = () => web3.Keypair.generate().secretKey.slice(0, 32)
+
+describe("private sale signature verification", () => {
+  const signer = web3.Keypair.generate()
+  const evmSigner = secp256k1Key()
+  let oldSigner: web3.PublicKey
+  let oldSecp256k1Signer: number[]
+  let mint: web3.PublicKey
+  let nonce = 1
+
+  const newPermit = async (): Promise<SalePermit> => ({
+    baseMint: mint,
+    trader: trader.publicKey,
+    phase: 0,
+    isBuy: true,
+    maxQuoteAmount: web3.LAMPORTS_PER_SOL,
+    expiry: (await chainTime()) + 60 * 60,
+    nonce: nonce++,
+  })
+
+  before(async () => {
+    await setup()
+    const state = await program.account.mainState.fetch(mainState)
+    oldSigner = state.signer
+    oldSecp256k1Signer = state.secp256k1Signer
+    await updateMainState({ signer: signer.publicKey })
+    mint = await createPoolMint(6, 1e15)
+    await createPool(mint, { privSalePeriod: 60 * 60 })
+  })
+
+  after(async () => {
+    await updateMainState({ signer: oldSigner, secp256k1Signer: oldSecp256k1Signer })
+  })
+
+  it("finds ed25519 instruction placed after compute budget instructions", async () => {
+    const permit = await newPermit()
+    const { ix, auth } = ed25519Permit(signer, permit)
+    await buy(trader, mint, web3.LAMPORTS_PER_SOL / 2, {
+      auth,
+      permitNonce: permitNonceOf(trader.publicKey, permit.nonce),
+      preInstructions: [
+        web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+        web3.ComputeBudgetProgram.setComputeUnitPrice({ microLamports: 1 }),
+        ix,
+      ],
+    })
+  })
+
+  it("accepts a permit signed in a multi-signature ed25519 instruction", async () => {
+    const permit = await newPermit()
+    const { auth } = ed25519Permit(signer, permit)
+    const ix = ed25519MultiSigInstruction([
+      { signer: web3.Keypair.generate(), message: Buffer.from("another message") },
+      { signer, message: encodePermit(permit) },
+    ])
+    await buy(trader, mint, web3.LAMPORTS_PER_SOL / 2, {
+      auth,
+      permitNonce: permitNonceOf(trader.publicKey, permit.nonce),
+      preInstructions: [ix],
+    })
+  })
+
+  describe("secp256k1 permits", () => {
+    // Compute budget instruction comes first, so the signature is read from instruction 1
+    const buyWithSecp256k1Permit = (permit: SalePermit, privateKey = evmSigner, lamports = web3.LAMPORTS_PER_SOL / 2) => {
+      const { ix, auth } = secp256k1Permit(privateKey, permit, 1)
+      return buy(trader, mint, lamports, {
+        auth,
+        permitNonce: permitNonceOf(trader.publicKey, permit.nonce),
+        preInstructions: [web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }), ix],
+      })
+    }
+
+    it("rejects secp256k1 permits while no signer is set", async () => {
+      await updateMainState({ secp256k1Signer: new Array(20).fill(0) })
+      await expectError(buyWithSecp256k1Permit(await newPermit()), "Secp256k1SignerNotSet")
+      await updateMainState({ secp256k1Signer: ethAddressOf(evmSigner) })
+    })
+
+    it("buys with a permit signed by the secp256k1 signer", async () => {
+      const permit = await newPermit()
+      await buyWithSecp256k1Permit(permit)
+
+      const permitNonce = await program.account.permitNonce.fetch(permitNonceOf(trader.publicKey, permit.nonce))
+      assert.equal(permitNonce.nonce.toNumber(), permit.nonce)
+    })
+
+    it("rejects replaying a secp256k1 permit", async () => {
+      const permit = await newPermit()
+      await buyWithSecp256k1Permit(permit)
+      await expectError(buyWithSecp256k1Permit(permit, evmSigner, web3.LAMPORTS_PER_SOL / 4), "PermitAlreadyUsed")
+    })
+
+    it("rejects a permit signed by another secp256k1 key", async () => {
+      await expectError(buyWithSecp256k1Permit(await newPermit(), secp256k1Key()), "SigVerificationFailed")
+    })
+
+    it("rejects an ed25519 signature passed as secp256k1 permit", async () => {
+      const permit = await newPermit()
+      const { ix, auth } = ed25519Permit(signer, permit)
+      await expectError(
+        buy(trader, mint, web3.LAMPORTS_PER_SOL / 2, {
+          auth: { secp256k1Permit: auth.permit },
+          permitNonce: permitNonceOf(trader.publicKey, permit.nonce),
+          preInstructions: [ix],
+        }),
+        "InvalidSigLen"
+      )
+    })
+  })
+})
@@ -0,0 +1,35 @@
+import { web3, BN } from "@coral-xyz/anchor";
+import { assert } from "chai";
+import {
+  FEE_PRE_DIV, program, connection, creator, trader, poolStateOf,
+  setup, createPoolMint, createPool, buy, updateTax, expectError,
+} from "./helper";
+
+describe("launch tax schedule updates", () => {
+  const schedule = { startRate: new BN(10 * FEE_PRE_DIV), endRate: new BN(10 * FEE_PRE_DIV), decayDuration: new BN(3600), curve: { linear: {} } }
+  let mint: web3.PublicKey
+
+  before(async () => {
+    await setup()
+    mint = await createPoolMint(6, 1e15)
+    await createPool(mint, { taxSchedule: schedule })
+  })
+
+  it("rejects a schedule whose start rate is below its end rate", async () => {
+    const inverted = { ...schedule, startRate: new BN(0) }
+    await expectError(updateTax(mint, 0, 0, inverted), "InvalidTaxSchedule")
+  })
+
+  it("rejects raising buy tax while launch tax decays", async () => {
+    await expectError(updateTax(mint, 1 * FEE_PRE_DIV, 0, null), "TaxScheduleActive")
+  })
+
+  it("keeps trading open with the launch tax applied", async () => {
+    const poolOwnerBalance = await connection.getBalance(creator.publicKey)
+    await buy(trader, mint, web3.LAMPORTS_PER_SOL / 2)
+
+    const poolState = await program.account.poolState.fetch(poolStateOf(mint))
+    assert.equal(poolState.tradeCount.toNumber(), 1)
+    assert.isAbove(await connection.getBalance(creator.publicKey), poolOwnerBalance)
+  })
+})